@@ -172,6 +172,100 @@ fn bench_parallel_any_all(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_parallel_collect(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collect_list");
+
+    for size in [1_000, 10_000, 100_000].iter() {
+        let list = create_list(*size);
+
+        group.bench_with_input(BenchmarkId::new("parallel", size), size, |b, _| {
+            b.iter(|| {
+                let result: XorLinkedList<i32> = list.par_iter().map(|&x| x * 2).collect();
+                black_box(result)
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("parallel_via_vec", size), size, |b, _| {
+            b.iter(|| {
+                let doubled: Vec<i32> = list.par_iter().map(|&x| x * 2).collect();
+                let mut result = XorLinkedList::new();
+                for x in doubled {
+                    result.push_back(x);
+                }
+                black_box(result)
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("sequential", size), size, |b, _| {
+            b.iter(|| {
+                let mut result = XorLinkedList::new();
+                for &x in list.iter() {
+                    result.push_back(x * 2);
+                }
+                black_box(result)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_parallel_split_index(c: &mut Criterion) {
+    let mut group = c.benchmark_group("split_index");
+
+    for size in [10_000, 100_000, 1_000_000].iter() {
+        let list = create_list(*size);
+        let index = list.split_index(256);
+
+        group.bench_with_input(BenchmarkId::new("parallel", size), size, |b, _| {
+            b.iter(|| {
+                let sum: i64 = list.par_iter().map(|&x| x as i64).sum();
+                black_box(sum)
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("parallel_indexed", size), size, |b, _| {
+            b.iter(|| {
+                let sum: i64 = index.par_iter().map(|&x| x as i64).sum();
+                black_box(sum)
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("sequential", size), size, |b, _| {
+            b.iter(|| {
+                let sum: i64 = list.iter().map(|&x| x as i64).sum();
+                black_box(sum)
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_parallel_from_vec(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from_vec");
+
+    for size in [10_000, 100_000, 1_000_000].iter() {
+        let vec: Vec<i32> = (0..*size).collect();
+
+        group.bench_with_input(BenchmarkId::new("parallel", size), size, |b, _| {
+            b.iter(|| black_box(XorLinkedList::par_from_slice(&vec)));
+        });
+
+        group.bench_with_input(BenchmarkId::new("sequential", size), size, |b, _| {
+            b.iter(|| {
+                let mut list = XorLinkedList::new();
+                for &x in vec.iter() {
+                    list.push_back(x);
+                }
+                black_box(list)
+            });
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_parallel_sum,
@@ -179,6 +273,9 @@ criterion_group!(
     bench_parallel_filter,
     bench_parallel_find,
     bench_parallel_fold_reduce,
-    bench_parallel_any_all
+    bench_parallel_any_all,
+    bench_parallel_collect,
+    bench_parallel_split_index,
+    bench_parallel_from_vec
 );
 criterion_main!(benches);
@@ -0,0 +1,91 @@
+use crate::xor_linked_list::{XorLinkedList, XorListError, expect_valid};
+
+// A list that rejects pushes past `max_nodes`. The limit lives here rather
+// than in XorLinkedList so unbounded lists keep their small header. Reading
+// goes through Deref, growing the list only through the checked methods.
+pub struct BoundedXorList<T> {
+    list: XorLinkedList<T>,
+    max_nodes: usize,
+}
+
+impl<T> BoundedXorList<T> {
+    pub fn new(max_nodes: usize) -> Self {
+        Self {
+            list: XorLinkedList::new(),
+            max_nodes,
+        }
+    }
+
+    pub fn capacity_limit(&self) -> usize {
+        self.max_nodes
+    }
+
+    // len() walks the list without `tracked_len`
+    fn check_limit(&self) -> Result<(), XorListError> {
+        if self.list.len() >= self.max_nodes {
+            return Err(XorListError::CapacityExceeded {
+                limit: self.max_nodes,
+            });
+        }
+        Ok(())
+    }
+
+    // panics when the list is full
+    pub fn push_back(&mut self, element: T) {
+        expect_valid(self.check_limit());
+        self.list.push_back(element);
+    }
+
+    pub fn push_front(&mut self, element: T) {
+        expect_valid(self.check_limit());
+        self.list.push_front(element);
+    }
+
+    pub fn try_push_back(&mut self, element: T) -> Result<(), XorListError> {
+        self.check_limit()?;
+        self.list.try_push_back(element)
+    }
+
+    pub fn try_push_front(&mut self, element: T) -> Result<(), XorListError> {
+        self.check_limit()?;
+        self.list.try_push_front(element)
+    }
+
+    // stops at the first failure, elements pushed before it stay in the list
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), XorListError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for element in iter {
+            self.try_push_back(element)?;
+        }
+        Ok(())
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    pub fn into_inner(self) -> XorLinkedList<T> {
+        self.list
+    }
+}
+
+impl<T> XorLinkedList<T> {
+    // the limit is kept by the returned wrapper, not by the list itself
+    pub fn with_capacity_limit(max_nodes: usize) -> BoundedXorList<T> {
+        BoundedXorList::new(max_nodes)
+    }
+}
+
+impl<T> core::ops::Deref for BoundedXorList<T> {
+    type Target = XorLinkedList<T>;
+
+    fn deref(&self) -> &XorLinkedList<T> {
+        &self.list
+    }
+}
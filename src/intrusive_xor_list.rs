@@ -0,0 +1,188 @@
+use crate::xor_linked_list::{
+    XorLink, XorLinked, expect_valid, get_next_linked, link_of, node_at, point_linked_a_to_b,
+};
+
+fn address<T>(item: &T) -> XorLink {
+    link_of(item)
+}
+
+// Safety: `ptr` is the address of an item borrowed for 'a by the list
+unsafe fn get_item_at_ptr<'a, T>(ptr: XorLink) -> &'a T {
+    unsafe { &*node_at::<T>(ptr) }
+}
+
+pub struct IntrusiveXorIter<'a, T>
+where
+    T: XorLinked + 'a,
+{
+    prev_ptr: XorLink,
+    curr_ptr: XorLink,
+    _phantom_data: core::marker::PhantomData<&'a T>,
+}
+
+impl<'a, T: XorLinked> IntrusiveXorIter<'a, T> {
+    fn new(list: &IntrusiveXorList<'a, T>, reverse: bool) -> IntrusiveXorIter<'a, T> {
+        IntrusiveXorIter {
+            prev_ptr: 0,
+            curr_ptr: if reverse { list.end } else { list.begin },
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T: XorLinked> Iterator for IntrusiveXorIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.curr_ptr == 0 {
+            return None;
+        }
+
+        // items are borrowed by the list for 'a
+        let item = unsafe { get_item_at_ptr::<T>(self.curr_ptr) };
+
+        let next_ptr = get_next_linked::<T>(self.prev_ptr, self.curr_ptr);
+        self.prev_ptr = self.curr_ptr;
+        self.curr_ptr = next_ptr;
+
+        Some(item)
+    }
+}
+
+// XOR list threaded through the caller's own objects, nothing is allocated.
+// Items are borrowed for 'a, so they can neither move nor be dropped while
+// linked, and each item can be part of at most one list at a time.
+pub struct IntrusiveXorList<'a, T>
+where
+    T: XorLinked + 'a,
+{
+    begin: XorLink,
+    end: XorLink,
+    _phantom_data: core::marker::PhantomData<&'a T>,
+}
+
+impl<'a, T: XorLinked> IntrusiveXorList<'a, T> {
+    pub fn new() -> Self {
+        Self {
+            begin: 0,
+            end: 0,
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+
+    pub fn iter(&self) -> IntrusiveXorIter<'a, T> {
+        IntrusiveXorIter::new(self, false)
+    }
+
+    pub fn iter_rev(&self) -> IntrusiveXorIter<'a, T> {
+        IntrusiveXorIter::new(self, true)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.begin == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn front(&self) -> Option<&'a T> {
+        (self.begin != 0).then(|| unsafe { get_item_at_ptr(self.begin) })
+    }
+
+    pub fn back(&self) -> Option<&'a T> {
+        (self.end != 0).then(|| unsafe { get_item_at_ptr(self.end) })
+    }
+
+    // the link may hold anything while the item is not in a list
+    fn link_unused(item: &'a T) -> XorLink {
+        assert!(!item.linked().get(), "item is already linked into a list");
+        item.linked().set(true);
+        item.xor_link().set(0);
+        address(item)
+    }
+
+    fn unlink(ptr: XorLink) -> &'a T {
+        // the item was pushed as a &'a T
+        let item = unsafe { get_item_at_ptr::<T>(ptr) };
+        item.linked().set(false);
+        item
+    }
+
+    pub fn push_back(&mut self, item: &'a T) {
+        let ptr = Self::link_unused(item);
+        if self.end == 0 {
+            // empty list
+            self.begin = ptr;
+            self.end = ptr;
+            return;
+        }
+        expect_valid(point_linked_a_to_b::<T>(self.end, ptr));
+        self.end = ptr;
+    }
+
+    pub fn push_front(&mut self, item: &'a T) {
+        let ptr = Self::link_unused(item);
+        if self.begin == 0 {
+            // empty list
+            self.begin = ptr;
+            self.end = ptr;
+            return;
+        }
+        expect_valid(point_linked_a_to_b::<T>(self.begin, ptr));
+        self.begin = ptr;
+    }
+
+    pub fn pop_back(&mut self) -> Option<&'a T> {
+        if self.end == 0 {
+            return None;
+        }
+
+        let old_end = self.end;
+        let prev_ptr = get_next_linked::<T>(0, old_end);
+        if prev_ptr == 0 {
+            // only one element
+            self.begin = 0;
+        } else {
+            expect_valid(point_linked_a_to_b::<T>(prev_ptr, old_end)); // clean current end
+        }
+        self.end = prev_ptr;
+
+        Some(Self::unlink(old_end))
+    }
+
+    pub fn pop_front(&mut self) -> Option<&'a T> {
+        if self.begin == 0 {
+            return None;
+        }
+
+        let old_begin = self.begin;
+        let next_ptr = get_next_linked::<T>(0, old_begin);
+        if next_ptr == 0 {
+            // only one element
+            self.end = 0;
+        } else {
+            expect_valid(point_linked_a_to_b::<T>(next_ptr, old_begin)); // clean current begin
+        }
+        self.begin = next_ptr;
+
+        Some(Self::unlink(old_begin))
+    }
+
+    // unlinks every item, leaving their links cleared for reuse
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<'a, T: XorLinked> Default for IntrusiveXorList<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: XorLinked> Drop for IntrusiveXorList<'a, T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
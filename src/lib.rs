@@ -1,5 +1,30 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod bounded_xor_list;
+mod intrusive_xor_list;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "snapshot")]
+mod snapshot;
 mod xor_linked_list;
-pub use xor_linked_list::XorLinkedList;
+#[cfg(feature = "std")]
+mod xor_lru_cache;
+mod xor_skip_list;
+mod xor_slice;
+pub use bounded_xor_list::BoundedXorList;
+pub use intrusive_xor_list::IntrusiveXorList;
+#[cfg(feature = "snapshot")]
+pub use snapshot::SnapshotError;
+#[cfg(feature = "debug_checks")]
+pub use xor_linked_list::StaleHandle;
+pub use xor_linked_list::{CorruptionError, NodeRef, XorLinked, XorLinkedList, XorListError};
+#[cfg(feature = "std")]
+pub use xor_lru_cache::XorLruCache;
+pub use xor_skip_list::{LevelGenerator, XorSkipList};
+pub use xor_slice::{XorSlice, XorSliceIter, XorSliceIterMut, XorSliceMut};
 #[cfg(feature = "parallel")]
 pub mod parallel;
-
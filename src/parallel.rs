@@ -1,186 +1,875 @@
-use crate::xor_linked_list::{XorLink, XorLinkedList, get_element_at_ptr, get_next_ptr};
+use crate::xor_linked_list::{
+    XorLink, XorLinkedList, get_element_at_ptr, get_element_at_ptr_mut, get_next_ptr,
+};
+use crate::xor_slice::{XorSlice, XorSliceIter, XorSliceIterMut, XorWindow, walk};
 use rayon::iter::plumbing::Consumer;
 use rayon::iter::plumbing::Producer;
 use rayon::iter::plumbing::ProducerCallback;
 use rayon::iter::plumbing::UnindexedConsumer;
-use rayon::iter::plumbing::bridge;
-use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use rayon::iter::plumbing::{Folder, UnindexedProducer, bridge, bridge_unindexed};
+use rayon::iter::{
+    FromParallelIterator, IndexedParallelIterator, IntoParallelIterator, ParallelExtend,
+    ParallelIterator,
+};
+use std::cmp::Ordering;
+use std::sync::Arc;
+use std::vec::Vec;
+
+// below this many elements a sort piece is merge sorted on the current worker,
+// Miri gets a small one so that short test lists still take the parallel path
+const SEQUENTIAL_SORT_THRESHOLD: usize = if cfg!(miri) { 64 } else { 4096 };
+
+// smallest run of nodes a single job allocates and links when building a list
+const MIN_BULK_CHUNK_LEN: usize = if cfg!(miri) { 16 } else { 1024 };
+
+// nodes a split walks into a list of unknown length, the walked prefix becomes
+// a sized piece and the rest is split again by whichever worker picks it up
+const UNSIZED_SPLIT_STEPS: usize = if cfg!(miri) { 16 } else { 1024 };
+
+// checkpoint spacing of the index recorded while counting an unsized list
+#[cfg(not(feature = "tracked_len"))]
+const DEFAULT_CHECKPOINT_STRIDE: usize = 256;
+
+// (prev, curr) pairs taken every `stride` nodes from the front of a list, so a
+// split can start walking close to its midpoint instead of at the window start
+struct XorCheckpoints {
+    stride: usize,
+    pairs: Vec<(XorLink, XorLink)>,
+}
+
+impl XorCheckpoints {
+    // walks the whole list once and returns the checkpoints with the length
+    fn build<T>(list: &XorLinkedList<T>, stride: usize) -> (XorCheckpoints, usize) {
+        assert!(stride > 0);
+        let mut pairs = Vec::new();
+        let mut prev_ptr = 0;
+        let mut curr_ptr = list.begin;
+        let mut length = 0;
+        while curr_ptr != 0 {
+            if length % stride == 0 {
+                pairs.push((prev_ptr, curr_ptr));
+            }
+            let next_ptr = get_next_ptr::<T>(prev_ptr, curr_ptr);
+            prev_ptr = curr_ptr;
+            curr_ptr = next_ptr;
+            length += 1;
+        }
+        (XorCheckpoints { stride, pairs }, length)
+    }
+
+    // the closest recorded position at or before `position`
+    fn seek(&self, position: usize) -> Option<(usize, XorLink, XorLink)> {
+        let index = (position / self.stride).min(self.pairs.len().checked_sub(1)?);
+        let (prev_ptr, curr_ptr) = self.pairs[index];
+        Some((index * self.stride, prev_ptr, curr_ptr))
+    }
+}
+
+// Reusable split index over a list that stays borrowed while the index lives,
+// amortizing the checkpoint walk over many parallel passes.
+pub struct XorSplitIndex<'a, T> {
+    checkpoints: Arc<XorCheckpoints>,
+    window: XorWindow,
+    _phantom_data: std::marker::PhantomData<&'a XorLinkedList<T>>,
+}
+
+impl<'a, T: std::marker::Sync> XorSplitIndex<'a, T> {
+    pub fn par_iter(&self) -> ParXorIter<'a, T> {
+        ParXorIter {
+            window: self.window,
+            checkpoints: Some(Arc::clone(&self.checkpoints)),
+            _phantom_data: self._phantom_data,
+        }
+    }
+}
+
+impl<T> XorLinkedList<T> {
+    // indexed parallel iteration, which has to count the list without `tracked_len`
+    pub fn par_iter_indexed(&self) -> ParXorIter<'_, T> {
+        ParXorIter::new(self)
+    }
+
+    pub fn par_iter_mut_indexed(&mut self) -> ParXorIterMut<'_, T> {
+        ParXorIterMut::new(self)
+    }
+
+    // contiguous chunks of `chunk_size` elements, the last one may be shorter
+    pub fn par_chunks(&self, chunk_size: usize) -> ParXorChunks<'_, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let (window, checkpoints) = XorWindow::from_list(self);
+        ParXorChunks {
+            window,
+            checkpoints,
+            chunk_size,
+            _phantom_data: std::marker::PhantomData,
+        }
+    }
+
+    /// Records a checkpoint every `stride` nodes, so that parallel passes through
+    /// the returned index split close to their midpoints.
+    ///
+    /// With `tracked_len`, the default, this is the only way to get checkpoints:
+    /// `par_iter()`, `par_iter_mut()` and `par_chunks()` know the length without
+    /// walking the list, and their splits walk from the nearest end of each
+    /// piece, O(n) per level of splitting. Building an index is a full walk of
+    /// its own, so it pays off when the index is reused over several passes.
+    pub fn split_index(&self, stride: usize) -> XorSplitIndex<'_, T> {
+        let (checkpoints, length) = XorCheckpoints::build(self, stride);
+        XorSplitIndex {
+            checkpoints: Arc::new(checkpoints),
+            window: XorWindow::with_length(self, length),
+            _phantom_data: std::marker::PhantomData,
+        }
+    }
+}
+
+impl XorWindow {
+    // Counting an unsized list walks it anyway, so checkpoints come almost for
+    // free. A stored length is used as is, see split_index for checkpoints then.
+    fn from_list<T>(list: &XorLinkedList<T>) -> (XorWindow, Option<Arc<XorCheckpoints>>) {
+        #[cfg(feature = "tracked_len")]
+        {
+            (XorWindow::with_length(list, list.len()), None)
+        }
+        #[cfg(not(feature = "tracked_len"))]
+        {
+            let (checkpoints, length) = XorCheckpoints::build(list, DEFAULT_CHECKPOINT_STRIDE);
+            (
+                XorWindow::with_length(list, length),
+                Some(Arc::new(checkpoints)),
+            )
+        }
+    }
+
+    fn split_near<T>(
+        self,
+        mid: usize,
+        checkpoints: Option<&XorCheckpoints>,
+    ) -> (XorWindow, XorWindow) {
+        let checkpoint = checkpoints.and_then(|checkpoints| checkpoints.seek(self.offset + mid));
+        self.split_at_from::<T>(mid, checkpoint)
+    }
+
+    // Splits a window of unknown length after walking at most `max_steps` of
+    // its nodes, so no split has to visit the whole list. A longer window gives
+    // up the walked prefix as a sized window and keeps the rest unsized, a
+    // shorter one comes back whole with its length known.
+    fn split_prefix<T>(self, max_steps: usize) -> (XorWindow, Option<XorWindow>) {
+        let (mut prev_ptr, mut curr_ptr) = (self.prev_start, self.start);
+        let mut steps = 0;
+        while steps < max_steps && curr_ptr != self.prev_end {
+            (prev_ptr, curr_ptr) = walk::<T>(prev_ptr, curr_ptr, 1);
+            steps += 1;
+        }
+        if curr_ptr == self.prev_end {
+            let whole = XorWindow {
+                length: steps,
+                ..self
+            };
+            return (whole, None);
+        }
+
+        let prefix = XorWindow {
+            prev_start: self.prev_start,
+            start: self.start,
+            end: prev_ptr,
+            prev_end: curr_ptr,
+            length: steps,
+            offset: self.offset,
+        };
+        let rest = XorWindow {
+            prev_start: prev_ptr,
+            start: curr_ptr,
+            end: self.end,
+            prev_end: self.prev_end,
+            length: 0,
+            offset: self.offset + steps,
+        };
+        (prefix, Some(rest))
+    }
+}
 
 pub struct ParXorIter<'a, T>
 where
     T: 'a,
 {
-    prev_start: XorLink,
-    start: XorLink,
-    end: XorLink,
-    prev_end: XorLink,
-    length: usize,
-    _phantom_data: &'a std::marker::PhantomData<T>,
+    window: XorWindow,
+    checkpoints: Option<Arc<XorCheckpoints>>,
+    _phantom_data: std::marker::PhantomData<&'a XorLinkedList<T>>,
 }
 
 impl<'a, T> ParXorIter<'a, T>
 where
     T: 'a,
 {
-    fn new(list: &XorLinkedList<T>) -> ParXorIter<'a, T> {
+    fn new(list: &'a XorLinkedList<T>) -> ParXorIter<'a, T> {
+        let (window, checkpoints) = XorWindow::from_list(list);
         ParXorIter {
-            prev_start: 0,
-            start: list.begin,
-            end: list.end,
-            prev_end: 0,
-            length: list.len(),
-            _phantom_data: &std::marker::PhantomData,
+            window,
+            checkpoints,
+            _phantom_data: std::marker::PhantomData,
         }
     }
     fn len(&self) -> usize {
-        self.length
+        self.window.length
     }
 }
 
-struct XorSeqIter<'a, T> {
-    prev_start: XorLink,
-    start: XorLink,
-    end: XorLink,
-    prev_end: XorLink,
-    length: usize,
-    _phantom_data: &'a std::marker::PhantomData<T>,
+struct XorProducer<'a, T> {
+    window: XorWindow,
+    checkpoints: Option<Arc<XorCheckpoints>>,
+    _phantom_data: std::marker::PhantomData<&'a XorLinkedList<T>>,
+}
+
+impl<'a, T: std::marker::Sync> Producer for XorProducer<'a, T> {
+    type Item = &'a T;
+    // has to implement Iterator
+    // this means we need separate struct from ParXorIter
+    // otherwise we cannot disambigious ParallelIterator and Iterator
+    type IntoIter = XorSliceIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        XorSliceIter::new(self.window)
+    }
+
+    fn split_at(self, mid: usize) -> (Self, Self) {
+        let (first_half, second_half) = self
+            .window
+            .split_near::<T>(mid, self.checkpoints.as_deref());
+        (
+            XorProducer {
+                window: first_half,
+                checkpoints: self.checkpoints.clone(),
+                _phantom_data: self._phantom_data,
+            },
+            XorProducer {
+                window: second_half,
+                checkpoints: self.checkpoints,
+                _phantom_data: self._phantom_data,
+            },
+        )
+    }
+}
+
+impl<'a, T> From<ParXorIter<'a, T>> for XorProducer<'a, T> {
+    fn from(iterator: ParXorIter<'a, T>) -> Self {
+        Self {
+            window: iterator.window,
+            checkpoints: iterator.checkpoints,
+            _phantom_data: iterator._phantom_data,
+        }
+    }
+}
+
+impl<'a, T: std::marker::Sync> ParallelIterator for ParXorIter<'a, T> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<'a, T: std::marker::Sync> IndexedParallelIterator for ParXorIter<'a, T> {
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        let producer = XorProducer::from(self);
+        callback.callback(producer)
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
 }
 
-impl<'a, T> XorSeqIter<'a, T>
+// without a stored length the list is only indexed on request, see par_iter_indexed
+impl<'a, T: std::marker::Sync> IntoParallelIterator for &'a XorLinkedList<T> {
+    #[cfg(feature = "tracked_len")]
+    type Iter = ParXorIter<'a, T>;
+    #[cfg(not(feature = "tracked_len"))]
+    type Iter = ParXorUnindexedIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        #[cfg(feature = "tracked_len")]
+        {
+            ParXorIter::new(self)
+        }
+        #[cfg(not(feature = "tracked_len"))]
+        {
+            ParXorUnindexedIter::new(self)
+        }
+    }
+}
+
+pub struct ParXorChunks<'a, T>
 where
     T: 'a,
 {
-    fn new(prod: &XorProducer<T>) -> XorSeqIter<'a, T> {
-        XorSeqIter {
-            prev_start: prod.prev_start,
-            start: prod.start,
-            end: prod.end,
-            prev_end: prod.prev_end,
-            length: prod.length,
-            _phantom_data: &std::marker::PhantomData,
-        }
+    window: XorWindow,
+    checkpoints: Option<Arc<XorCheckpoints>>,
+    chunk_size: usize,
+    _phantom_data: std::marker::PhantomData<&'a XorLinkedList<T>>,
+}
+
+impl<'a, T> ParXorChunks<'a, T> {
+    fn len(&self) -> usize {
+        self.window.length.div_ceil(self.chunk_size)
+    }
+}
+
+struct XorChunksIter<'a, T> {
+    window: XorWindow,
+    chunk_size: usize,
+    _phantom_data: std::marker::PhantomData<&'a XorLinkedList<T>>,
+}
+
+impl<'a, T> XorChunksIter<'a, T> {
+    fn slice(&self, window: XorWindow) -> XorSlice<'a, T> {
+        XorSlice::new(window)
     }
 }
 
-impl<'a, T> ExactSizeIterator for XorSeqIter<'a, T> {
+impl<'a, T> ExactSizeIterator for XorChunksIter<'a, T> {
     fn len(&self) -> usize {
-        self.length
+        self.window.length.div_ceil(self.chunk_size)
     }
 }
 
-impl<'a, T> Iterator for XorSeqIter<'a, T> {
-    type Item = &'a T;
+impl<'a, T> Iterator for XorChunksIter<'a, T> {
+    type Item = XorSlice<'a, T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.start == 0 || self.start == self.prev_end {
+        if self.window.length == 0 {
             return None;
         }
+        let mid = self.chunk_size.min(self.window.length);
+        let (chunk, rest) = self.window.split_at::<T>(mid);
+        self.window = rest;
+        Some(self.slice(chunk))
+    }
 
-        let element = get_element_at_ptr::<T>(self.start);
-
-        let next_ptr = get_next_ptr::<T>(self.prev_start, self.start);
-        self.prev_start = self.start;
-        self.start = next_ptr;
-
-        Some(element)
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
     }
 }
 
-impl<'a, T> DoubleEndedIterator for XorSeqIter<'a, T> {
+impl<'a, T> DoubleEndedIterator for XorChunksIter<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.end == 0 || self.end == self.prev_start {
+        if self.window.length == 0 {
             return None;
         }
+        // the shorter chunk, if any, is the last one
+        let last = match self.window.length % self.chunk_size {
+            0 => self.chunk_size,
+            remainder => remainder,
+        };
+        let (rest, chunk) = self.window.split_at::<T>(self.window.length - last);
+        self.window = rest;
+        Some(self.slice(chunk))
+    }
+}
 
-        let element = get_element_at_ptr::<T>(self.end);
+struct XorChunksProducer<'a, T> {
+    window: XorWindow,
+    checkpoints: Option<Arc<XorCheckpoints>>,
+    chunk_size: usize,
+    _phantom_data: std::marker::PhantomData<&'a XorLinkedList<T>>,
+}
 
-        let next_ptr = get_next_ptr::<T>(self.prev_end, self.end);
-        self.prev_end = self.end;
-        self.end = next_ptr;
+impl<'a, T: std::marker::Sync> Producer for XorChunksProducer<'a, T> {
+    type Item = XorSlice<'a, T>;
+    type IntoIter = XorChunksIter<'a, T>;
 
-        Some(element)
+    fn into_iter(self) -> Self::IntoIter {
+        XorChunksIter {
+            window: self.window,
+            chunk_size: self.chunk_size,
+            _phantom_data: self._phantom_data,
+        }
+    }
+
+    // `mid` counts chunks
+    fn split_at(self, mid: usize) -> (Self, Self) {
+        let elements = (mid * self.chunk_size).min(self.window.length);
+        let (first_half, second_half) = self
+            .window
+            .split_near::<T>(elements, self.checkpoints.as_deref());
+        (
+            XorChunksProducer {
+                window: first_half,
+                checkpoints: self.checkpoints.clone(),
+                chunk_size: self.chunk_size,
+                _phantom_data: self._phantom_data,
+            },
+            XorChunksProducer {
+                window: second_half,
+                checkpoints: self.checkpoints,
+                chunk_size: self.chunk_size,
+                _phantom_data: self._phantom_data,
+            },
+        )
     }
 }
 
-struct XorProducer<'a, T> {
-    prev_start: XorLink,
-    start: XorLink,
-    end: XorLink,
-    prev_end: XorLink,
-    length: usize,
-    _phantom_data: &'a std::marker::PhantomData<T>,
+impl<'a, T: std::marker::Sync> ParallelIterator for ParXorChunks<'a, T> {
+    type Item = XorSlice<'a, T>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
 }
 
-impl<'a, T: std::marker::Sync> Producer for XorProducer<'a, T> {
+impl<'a, T: std::marker::Sync> IndexedParallelIterator for ParXorChunks<'a, T> {
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(XorChunksProducer {
+            window: self.window,
+            checkpoints: self.checkpoints,
+            chunk_size: self.chunk_size,
+            _phantom_data: self._phantom_data,
+        })
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+pub struct ParXorUnindexedIter<'a, T>
+where
+    T: 'a,
+{
+    window: XorWindow,
+    _phantom_data: std::marker::PhantomData<&'a XorLinkedList<T>>,
+}
+
+impl<'a, T> ParXorUnindexedIter<'a, T>
+where
+    T: 'a,
+{
+    #[cfg_attr(feature = "tracked_len", allow(dead_code))]
+    fn new(list: &'a XorLinkedList<T>) -> ParXorUnindexedIter<'a, T> {
+        ParXorUnindexedIter {
+            window: XorWindow::with_length(list, 0),
+            _phantom_data: std::marker::PhantomData,
+        }
+    }
+}
+
+// `sized` tells whether window.length is known, a split of an unsized window
+// only walks a bounded prefix of it
+struct XorUnindexedProducer<'a, T> {
+    window: XorWindow,
+    sized: bool,
+    _phantom_data: std::marker::PhantomData<&'a XorLinkedList<T>>,
+}
+
+impl<'a, T: std::marker::Sync> UnindexedProducer for XorUnindexedProducer<'a, T> {
+    type Item = &'a T;
+
+    fn split(self) -> (Self, Option<Self>) {
+        let window = if self.sized {
+            self.window
+        } else {
+            match self.window.split_prefix::<T>(UNSIZED_SPLIT_STEPS) {
+                (prefix, Some(rest)) => {
+                    let first = XorUnindexedProducer {
+                        window: prefix,
+                        sized: true,
+                        _phantom_data: std::marker::PhantomData,
+                    };
+                    let second = XorUnindexedProducer {
+                        window: rest,
+                        sized: false,
+                        _phantom_data: std::marker::PhantomData,
+                    };
+                    return (first, Some(second));
+                }
+                (whole, None) => whole,
+            }
+        };
+        if window.length < 2 {
+            let whole = XorUnindexedProducer {
+                window,
+                sized: true,
+                _phantom_data: std::marker::PhantomData,
+            };
+            return (whole, None);
+        }
+        let (first_half, second_half) = window.split_at::<T>(window.length / 2);
+        let first = XorUnindexedProducer {
+            window: first_half,
+            sized: true,
+            _phantom_data: std::marker::PhantomData,
+        };
+        let second = XorUnindexedProducer {
+            window: second_half,
+            sized: true,
+            _phantom_data: std::marker::PhantomData,
+        };
+        (first, Some(second))
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        let mut window = self.window;
+        folder.consume_iter(std::iter::from_fn(|| {
+            window
+                .advance::<T>()
+                .map(|ptr| unsafe { get_element_at_ptr::<T>(ptr) })
+        }))
+    }
+}
+
+impl<'a, T: std::marker::Sync> ParallelIterator for ParXorUnindexedIter<'a, T> {
     type Item = &'a T;
-    // has to implement Iterator
-    // this means we need separate struct from ParXorIter
-    // otherwise we cannot disambigious ParallelIterator and Iterator
-    type IntoIter = XorSeqIter<'a, T>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let producer = XorUnindexedProducer {
+            window: self.window,
+            sized: false,
+            _phantom_data: self._phantom_data,
+        };
+        bridge_unindexed(producer, consumer)
+    }
+}
+
+pub struct ParXorIterMut<'a, T>
+where
+    T: 'a,
+{
+    window: XorWindow,
+    checkpoints: Option<Arc<XorCheckpoints>>,
+    _phantom_data: std::marker::PhantomData<&'a mut XorLinkedList<T>>,
+}
+
+impl<'a, T> ParXorIterMut<'a, T>
+where
+    T: 'a,
+{
+    fn new(list: &'a mut XorLinkedList<T>) -> ParXorIterMut<'a, T> {
+        let (window, checkpoints) = XorWindow::from_list(list);
+        ParXorIterMut {
+            window,
+            checkpoints,
+            _phantom_data: std::marker::PhantomData,
+        }
+    }
+    fn len(&self) -> usize {
+        self.window.length
+    }
+}
+
+struct XorProducerMut<'a, T> {
+    window: XorWindow,
+    checkpoints: Option<Arc<XorCheckpoints>>,
+    _phantom_data: std::marker::PhantomData<&'a mut XorLinkedList<T>>,
+}
+
+impl<'a, T: std::marker::Send> Producer for XorProducerMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = XorSliceIterMut<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        XorSeqIter::new(&self)
+        XorSliceIterMut::new(self.window)
     }
 
     fn split_at(self, mid: usize) -> (Self, Self) {
-        let length = self.length;
-        assert!(mid <= length);
-        // first half [0, mid)
-        let first_prev_start = self.prev_start;
-        let first_start = self.start;
-
-        // we need pointer before and at mid
-        let mut prev_ptr = self.prev_start;
-        let mut curr_ptr = self.start;
-        for _ in 0..mid {
-            let next_ptr = get_next_ptr::<T>(prev_ptr, curr_ptr);
-            prev_ptr = curr_ptr;
-            curr_ptr = next_ptr;
+        let (first_half, second_half) = self
+            .window
+            .split_near::<T>(mid, self.checkpoints.as_deref());
+        (
+            XorProducerMut {
+                window: first_half,
+                checkpoints: self.checkpoints.clone(),
+                _phantom_data: std::marker::PhantomData,
+            },
+            XorProducerMut {
+                window: second_half,
+                checkpoints: self.checkpoints,
+                _phantom_data: std::marker::PhantomData,
+            },
+        )
+    }
+}
+
+impl<'a, T> From<ParXorIterMut<'a, T>> for XorProducerMut<'a, T> {
+    fn from(iterator: ParXorIterMut<'a, T>) -> Self {
+        Self {
+            window: iterator.window,
+            checkpoints: iterator.checkpoints,
+            _phantom_data: iterator._phantom_data,
         }
-        let prev_mid = prev_ptr;
-        let mid_ptr = curr_ptr;
+    }
+}
 
-        let first_half = XorProducer {
-            prev_start: first_prev_start,
-            start: first_start,
-            end: prev_mid,
-            prev_end: mid_ptr,
-            length: mid,
-            _phantom_data: self._phantom_data,
+impl<'a, T: std::marker::Send> ParallelIterator for ParXorIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<'a, T: std::marker::Send> IndexedParallelIterator for ParXorIterMut<'a, T> {
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        let producer = XorProducerMut::from(self);
+        callback.callback(producer)
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<'a, T: std::marker::Send> IntoParallelIterator for &'a mut XorLinkedList<T> {
+    #[cfg(feature = "tracked_len")]
+    type Iter = ParXorIterMut<'a, T>;
+    #[cfg(not(feature = "tracked_len"))]
+    type Iter = ParXorUnindexedIterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        #[cfg(feature = "tracked_len")]
+        {
+            ParXorIterMut::new(self)
+        }
+        #[cfg(not(feature = "tracked_len"))]
+        {
+            ParXorUnindexedIterMut::new(self)
+        }
+    }
+}
+
+pub struct ParXorUnindexedIterMut<'a, T>
+where
+    T: 'a,
+{
+    window: XorWindow,
+    _phantom_data: std::marker::PhantomData<&'a mut XorLinkedList<T>>,
+}
+
+impl<'a, T> ParXorUnindexedIterMut<'a, T>
+where
+    T: 'a,
+{
+    #[cfg_attr(feature = "tracked_len", allow(dead_code))]
+    fn new(list: &'a mut XorLinkedList<T>) -> ParXorUnindexedIterMut<'a, T> {
+        ParXorUnindexedIterMut {
+            window: XorWindow::with_length(list, 0),
+            _phantom_data: std::marker::PhantomData,
+        }
+    }
+}
+
+struct XorUnindexedProducerMut<'a, T> {
+    window: XorWindow,
+    sized: bool,
+    _phantom_data: std::marker::PhantomData<&'a mut XorLinkedList<T>>,
+}
+
+impl<'a, T: std::marker::Send> UnindexedProducer for XorUnindexedProducerMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn split(self) -> (Self, Option<Self>) {
+        let window = if self.sized {
+            self.window
+        } else {
+            match self.window.split_prefix::<T>(UNSIZED_SPLIT_STEPS) {
+                (prefix, Some(rest)) => {
+                    let first = XorUnindexedProducerMut {
+                        window: prefix,
+                        sized: true,
+                        _phantom_data: std::marker::PhantomData,
+                    };
+                    let second = XorUnindexedProducerMut {
+                        window: rest,
+                        sized: false,
+                        _phantom_data: std::marker::PhantomData,
+                    };
+                    return (first, Some(second));
+                }
+                (whole, None) => whole,
+            }
         };
+        if window.length < 2 {
+            let whole = XorUnindexedProducerMut {
+                window,
+                sized: true,
+                _phantom_data: std::marker::PhantomData,
+            };
+            return (whole, None);
+        }
+        let (first_half, second_half) = window.split_at::<T>(window.length / 2);
+        let first = XorUnindexedProducerMut {
+            window: first_half,
+            sized: true,
+            _phantom_data: std::marker::PhantomData,
+        };
+        let second = XorUnindexedProducerMut {
+            window: second_half,
+            sized: true,
+            _phantom_data: std::marker::PhantomData,
+        };
+        (first, Some(second))
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        let mut window = self.window;
+        folder.consume_iter(std::iter::from_fn(|| {
+            window
+                .advance::<T>()
+                .map(|ptr| unsafe { get_element_at_ptr_mut::<T>(ptr) })
+        }))
+    }
+}
 
-        // second half [mid, length)
-        let second_end = self.end;
-        let second_prev_end = self.prev_end;
+impl<'a, T: std::marker::Send> ParallelIterator for ParXorUnindexedIterMut<'a, T> {
+    type Item = &'a mut T;
 
-        let second_half = XorProducer {
-            prev_start: prev_mid,
-            start: mid_ptr,
-            end: second_end,
-            prev_end: second_prev_end,
-            length: length - mid,
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let producer = XorUnindexedProducerMut {
+            window: self.window,
+            sized: false,
             _phantom_data: self._phantom_data,
         };
+        bridge_unindexed(producer, consumer)
+    }
+}
 
-        (first_half, second_half)
+pub struct ParXorIntoIter<T> {
+    list: XorLinkedList<T>,
+    length: usize,
+}
+
+impl<T> ParXorIntoIter<T> {
+    fn new(list: XorLinkedList<T>) -> ParXorIntoIter<T> {
+        let length = list.len();
+        ParXorIntoIter { list, length }
     }
 }
 
-impl<'a, T> From<ParXorIter<'a, T>> for XorProducer<'a, T> {
-    fn from(iterator: ParXorIter<'a, T>) -> Self {
+// drains a standalone chain, whatever is left over is freed when the list drops
+struct XorSeqIntoIter<T> {
+    list: XorLinkedList<T>,
+    length: usize,
+}
+
+impl<T> ExactSizeIterator for XorSeqIntoIter<T> {
+    fn len(&self) -> usize {
+        self.length
+    }
+}
+
+impl<T> Iterator for XorSeqIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self.list.pop_front()?;
+        self.length -= 1;
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+impl<T> DoubleEndedIterator for XorSeqIntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let element = self.list.pop_back()?;
+        self.length -= 1;
+        Some(element)
+    }
+}
+
+// owns its nodes, every split cuts the chain so both halves stand on their own
+struct XorIntoProducer<T> {
+    list: XorLinkedList<T>,
+    length: usize,
+}
+
+impl<T: std::marker::Send> Producer for XorIntoProducer<T> {
+    type Item = T;
+    type IntoIter = XorSeqIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        XorSeqIntoIter {
+            list: self.list,
+            length: self.length,
+        }
+    }
+
+    fn split_at(mut self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.length);
+        let second_half = self.list.split_off(mid);
+        (
+            XorIntoProducer {
+                list: self.list,
+                length: mid,
+            },
+            XorIntoProducer {
+                list: second_half,
+                length: self.length - mid,
+            },
+        )
+    }
+}
+
+impl<T> From<ParXorIntoIter<T>> for XorIntoProducer<T> {
+    fn from(iterator: ParXorIntoIter<T>) -> Self {
         Self {
-            prev_start: iterator.prev_start,
-            start: iterator.start,
-            end: iterator.end,
-            prev_end: iterator.prev_end,
+            list: iterator.list,
             length: iterator.length,
-            _phantom_data: iterator._phantom_data,
         }
     }
 }
 
-impl<'a, T: std::marker::Sync> ParallelIterator for ParXorIter<'a, T> {
-    type Item = &'a T;
+impl<T: std::marker::Send> ParallelIterator for ParXorIntoIter<T> {
+    type Item = T;
 
     fn drive_unindexed<C>(self, consumer: C) -> C::Result
     where
@@ -190,13 +879,13 @@ impl<'a, T: std::marker::Sync> ParallelIterator for ParXorIter<'a, T> {
     }
 
     fn opt_len(&self) -> Option<usize> {
-        Some(self.len())
+        Some(self.length)
     }
 }
 
-impl<'a, T: std::marker::Sync> IndexedParallelIterator for ParXorIter<'a, T> {
+impl<T: std::marker::Send> IndexedParallelIterator for ParXorIntoIter<T> {
     fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
-        let producer = XorProducer::from(self);
+        let producer = XorIntoProducer::from(self);
         callback.callback(producer)
     }
 
@@ -205,15 +894,165 @@ impl<'a, T: std::marker::Sync> IndexedParallelIterator for ParXorIter<'a, T> {
     }
 
     fn len(&self) -> usize {
-        self.len()
+        self.length
     }
 }
 
-impl<'a, T: std::marker::Sync> IntoParallelIterator for &'a XorLinkedList<T> {
-    type Iter = ParXorIter<'a, T>;
-    type Item = &'a T;
+impl<T: std::marker::Send> IntoParallelIterator for XorLinkedList<T> {
+    type Iter = ParXorIntoIter<T>;
+    type Item = T;
 
     fn into_par_iter(self) -> Self::Iter {
-        ParXorIter::new(self)
+        ParXorIntoIter::new(self)
+    }
+}
+
+impl<T: std::marker::Send> FromParallelIterator<T> for XorLinkedList<T> {
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        let mut list = XorLinkedList::new();
+        list.par_extend(par_iter);
+        list
+    }
+}
+
+impl<T: std::marker::Send> ParallelExtend<T> for XorLinkedList<T> {
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        // one list per job, neighbouring jobs are spliced together in order
+        let mut other = par_iter
+            .into_par_iter()
+            .fold(XorLinkedList::new, |mut list, element| {
+                list.push_back(element);
+                list
+            })
+            .reduce(XorLinkedList::new, |mut first, mut second| {
+                first.append(&mut second);
+                first
+            });
+        self.append(&mut other);
+    }
+}
+
+impl<T: std::marker::Send> XorLinkedList<T> {
+    // every job links the nodes of one chunk, chunks are spliced at their ends
+    pub fn par_from_vec(vec: Vec<T>) -> Self {
+        vec.into_par_iter()
+            .with_min_len(MIN_BULK_CHUNK_LEN)
+            .collect()
+    }
+
+    pub fn par_from_slice(slice: &[T]) -> Self
+    where
+        T: Clone + Sync,
+    {
+        slice
+            .into_par_iter()
+            .with_min_len(MIN_BULK_CHUNK_LEN)
+            .cloned()
+            .collect()
+    }
+
+    pub fn par_sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.par_sort_by(T::cmp);
+    }
+
+    // Stable merge sort that relinks the existing nodes instead of moving
+    // elements. If `compare` panics the list keeps all of its elements, in an
+    // unspecified order.
+    pub fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        let length = self.len();
+        par_merge_sort(self, length, &compare);
+    }
+}
+
+// Merges the sorted `second` into the sorted `target`. Whatever is left is
+// linked back into `target` when it is dropped, so every node ends up in
+// `target` even when `compare` panics half way.
+struct Merge<'a, T> {
+    target: &'a mut XorLinkedList<T>,
+    second: XorLinkedList<T>,
+    merged: XorLinkedList<T>,
+}
+
+impl<T> Merge<'_, T> {
+    // ties are taken from `target`, which keeps the sort stable
+    fn run<F>(&mut self, compare: &F)
+    where
+        F: Fn(&T, &T) -> Ordering,
+    {
+        let first = &mut *self.target;
+        let second = &mut self.second;
+        while first.begin != 0 && second.begin != 0 {
+            let first_element = first.element(first.begin);
+            let second_element = second.element(second.begin);
+            if compare(second_element, first_element) == Ordering::Less {
+                second.move_front_to(&mut self.merged);
+            } else {
+                first.move_front_to(&mut self.merged);
+            }
+        }
+    }
+}
+
+impl<T> Drop for Merge<'_, T> {
+    fn drop(&mut self) {
+        // split_off(0) moves the unmerged nodes out and leaves target its header
+        let mut rest = self.target.split_off(0);
+        self.target.append(&mut self.merged);
+        self.target.append(&mut rest);
+        self.target.append(&mut self.second);
+    }
+}
+
+fn par_merge_sort<T, F>(list: &mut XorLinkedList<T>, length: usize, compare: &F)
+where
+    T: std::marker::Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    if length <= SEQUENTIAL_SORT_THRESHOLD {
+        merge_sort(list, length, compare);
+        return;
+    }
+    let mid = length / 2;
+    let second = list.split_off(mid);
+    let mut merge = Merge {
+        target: list,
+        second,
+        merged: XorLinkedList::new(),
+    };
+    rayon::join(
+        || par_merge_sort(merge.target, mid, compare),
+        || par_merge_sort(&mut merge.second, length - mid, compare),
+    );
+    merge.run(compare);
+}
+
+fn merge_sort<T, F>(list: &mut XorLinkedList<T>, length: usize, compare: &F)
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    if length <= 1 {
+        return;
     }
+    let mid = length / 2;
+    let second = list.split_off(mid);
+    let mut merge = Merge {
+        target: list,
+        second,
+        merged: XorLinkedList::new(),
+    };
+    merge_sort(merge.target, mid, compare);
+    merge_sort(&mut merge.second, length - mid, compare);
+    merge.run(compare);
 }
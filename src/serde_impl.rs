@@ -0,0 +1,53 @@
+use crate::xor_linked_list::XorLinkedList;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+// a plain sequence, so the format is the same as for a Vec<T>
+impl<T: Serialize> Serialize for XorLinkedList<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for element in self.iter() {
+            seq.serialize_element(element)?;
+        }
+        seq.end()
+    }
+}
+
+struct XorLinkedListVisitor<T> {
+    _phantom_data: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for XorLinkedListVisitor<T> {
+    type Value = XorLinkedList<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut list = XorLinkedList::new();
+        while let Some(element) = seq.next_element()? {
+            list.push_back(element);
+        }
+        Ok(list)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for XorLinkedList<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(XorLinkedListVisitor {
+            _phantom_data: PhantomData,
+        })
+    }
+}
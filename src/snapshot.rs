@@ -0,0 +1,168 @@
+use crate::xor_linked_list::{XorLinkedList, XorNode};
+use bytemuck::Pod;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+// Layout, header integers are little endian:
+//   magic "XORL" | version u32 | length u64 | element size u64
+//   | length payloads in list order | FNV-1a 64 of everything before it
+// Payloads are the raw bytes of T, so snapshots only move between machines
+// of the same byte order. Zero sized payloads leave nothing to read that would
+// bound the length, so for those the checksum is checked before any node is
+// allocated.
+const MAGIC: [u8; 4] = *b"XORL";
+const VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    BadMagic([u8; 4]),
+    UnsupportedVersion(u32),
+    ElementSizeMismatch { expected: u64, found: u64 },
+    // more nodes than fit in the address space
+    LengthTooLarge(u64),
+    ChecksumMismatch { expected: u64, found: u64 },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(error) => write!(f, "snapshot i/o failed: {error}"),
+            SnapshotError::BadMagic(magic) => write!(f, "not a list snapshot, magic {magic:?}"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {version}")
+            }
+            SnapshotError::ElementSizeMismatch { expected, found } => write!(
+                f,
+                "snapshot holds {found} byte elements, expected {expected} bytes"
+            ),
+            SnapshotError::LengthTooLarge(length) => {
+                write!(f, "snapshot length {length} does not fit in memory")
+            }
+            SnapshotError::ChecksumMismatch { expected, found } => write!(
+                f,
+                "snapshot checksum {found:#018x} does not match {expected:#018x}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        SnapshotError::Io(error)
+    }
+}
+
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+fn write_hashed(writer: &mut impl Write, hash: &mut Fnv1a, bytes: &[u8]) -> io::Result<()> {
+    hash.update(bytes);
+    writer.write_all(bytes)
+}
+
+fn read_hashed(reader: &mut impl Read, hash: &mut Fnv1a, bytes: &mut [u8]) -> io::Result<()> {
+    reader.read_exact(bytes)?;
+    hash.update(bytes);
+    Ok(())
+}
+
+fn read_u64(reader: &mut impl Read, hash: &mut Fnv1a) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    read_hashed(reader, hash, &mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_checksum(reader: &mut impl Read, hash: &Fnv1a) -> Result<(), SnapshotError> {
+    let mut checksum = [0; 8];
+    reader.read_exact(&mut checksum)?;
+    let checksum = u64::from_le_bytes(checksum);
+    if checksum != hash.0 {
+        return Err(SnapshotError::ChecksumMismatch {
+            expected: hash.0,
+            found: checksum,
+        });
+    }
+    Ok(())
+}
+
+impl<T: Pod> XorLinkedList<T> {
+    // streams the elements straight from the nodes
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<(), SnapshotError> {
+        let mut hash = Fnv1a::new();
+        write_hashed(writer, &mut hash, &MAGIC)?;
+        write_hashed(writer, &mut hash, &VERSION.to_le_bytes())?;
+        write_hashed(writer, &mut hash, &(self.len() as u64).to_le_bytes())?;
+        let element_size = std::mem::size_of::<T>() as u64;
+        write_hashed(writer, &mut hash, &element_size.to_le_bytes())?;
+        for element in self.iter() {
+            write_hashed(writer, &mut hash, bytemuck::bytes_of(element))?;
+        }
+        writer.write_all(&hash.0.to_le_bytes())?;
+        Ok(())
+    }
+
+    // every payload is read directly into its freshly linked node
+    pub fn read_from(reader: &mut impl Read) -> Result<Self, SnapshotError> {
+        let mut hash = Fnv1a::new();
+        let mut magic = [0; 4];
+        read_hashed(reader, &mut hash, &mut magic)?;
+        if magic != MAGIC {
+            return Err(SnapshotError::BadMagic(magic));
+        }
+        let mut version = [0; 4];
+        read_hashed(reader, &mut hash, &mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let length = read_u64(reader, &mut hash)?;
+        let element_size = read_u64(reader, &mut hash)?;
+        let expected_size = std::mem::size_of::<T>() as u64;
+        if element_size != expected_size {
+            return Err(SnapshotError::ElementSizeMismatch {
+                expected: expected_size,
+                found: element_size,
+            });
+        }
+        let max_length = isize::MAX as u64 / std::mem::size_of::<XorNode<T>>() as u64;
+        if length > max_length {
+            return Err(SnapshotError::LengthTooLarge(length));
+        }
+        if element_size == 0 {
+            read_checksum(reader, &hash)?;
+        }
+
+        let mut list = XorLinkedList::new();
+        for _ in 0..length {
+            let element = list.push_back_mut(T::zeroed());
+            read_hashed(reader, &mut hash, bytemuck::bytes_of_mut(element))?;
+        }
+
+        if element_size != 0 {
+            read_checksum(reader, &hash)?;
+        }
+        Ok(list)
+    }
+}
@@ -1,74 +1,144 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::Cell;
+
+// with `debug_checks_amortized`, lists up to this length are still validated
+// after every mutation, longer ones only once every len mutations
+#[cfg(feature = "debug_checks_amortized")]
+const VALIDATE_ALWAYS_LEN: usize = 64;
+
+#[cfg(feature = "debug_checks")]
+static NEXT_GENERATION: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(1);
+
+/// Anything that can be threaded into an XOR chain: the link holds the XOR of
+/// the addresses of its two neighbours, 0 standing for "no neighbour". A link
+/// of 0 is also what the only item of a list holds, so membership is tracked
+/// separately by `linked`.
+///
+/// # Safety
+///
+/// `xor_link` and `linked` must always return the same cells, owned by `self`
+/// and not touched by anything but the list the item is linked into. `linked`
+/// must be false for an item that was never linked. The link can start out
+/// with any value, a list clears it when the item is pushed.
+pub unsafe trait XorLinked {
+    fn xor_link(&self) -> &Cell<usize>;
+    fn linked(&self) -> &Cell<bool>;
+}
+
 pub(crate) struct XorNode<T> {
     elem: T,
-    xor_pointer: usize,
+    xor_pointer: Cell<usize>,
+    // unique per allocation, tells a reused address apart from the node a handle was made for
+    #[cfg(feature = "debug_checks")]
+    generation: usize,
 }
 
 impl<T> XorNode<T> {
     pub fn new(element: T) -> Self {
         Self {
             elem: element,
-            xor_pointer: 0,
+            xor_pointer: Cell::new(0),
+            #[cfg(feature = "debug_checks")]
+            generation: NEXT_GENERATION.fetch_add(1, core::sync::atomic::Ordering::Relaxed),
         }
     }
+
+    pub(crate) fn into_element(self) -> T {
+        self.elem
+    }
 }
+
 pub(crate) type XorLink = usize;
 
+// The XOR of two addresses cannot carry the provenance of either, so every
+// node's address is exposed when it becomes a link, and a link is turned back
+// into a pointer through the exposed provenance of the node at that address.
+// Miri accepts this with MIRIFLAGS=-Zmiri-permissive-provenance.
+pub(crate) fn link_of<N>(ptr: *const N) -> XorLink {
+    ptr.expose_provenance()
+}
+
+pub(crate) fn node_at<N>(link: XorLink) -> *mut N {
+    core::ptr::with_exposed_provenance_mut(link)
+}
+
+// Borrows only the link field, so it can be used while elements are borrowed.
+//
+// Safety: `ptr` is a live node that stays allocated for 'a.
+unsafe fn node_link<'a, T>(ptr: XorLink) -> &'a Cell<usize> {
+    unsafe { &(*node_at::<XorNode<T>>(ptr)).xor_pointer }
+}
+
 pub(crate) fn get_next_ptr<T>(prev_ptr: XorLink, curr: XorLink) -> XorLink {
-    let node = unsafe { &*(curr as *const XorNode<T>) };
-    let xor_pointer = node.xor_pointer;
+    prev_ptr ^ unsafe { node_link::<T>(curr) }.get()
+}
+
+pub(crate) fn get_next_linked<N: XorLinked>(prev_ptr: XorLink, curr: XorLink) -> XorLink {
+    let node = unsafe { &*node_at::<N>(curr) };
+    let xor_pointer = node.xor_link().get();
     prev_ptr ^ xor_pointer
 }
 
-pub(crate) fn get_element_at_ptr<'a, T>(ptr: XorLink) -> &'a T {
-    unsafe {
-        let node = &*(ptr as *const XorNode<T>);
-        &node.elem
-    }
+#[cfg(feature = "debug_checks")]
+fn get_generation<T>(ptr: XorLink) -> usize {
+    unsafe { (*node_at::<XorNode<T>>(ptr)).generation }
 }
 
-pub(crate) fn consume_element_at_ptr<T>(ptr: XorLink) -> T {
-    unsafe {
-        let boxed_node = Box::from_raw(ptr as *mut XorNode<T>);
-        boxed_node.elem
-    }
+// The element of a live node, borrowed for as long as the caller borrows the
+// structure owning it. Code holding that structure should go through
+// XorLinkedList::element(_mut), which ties 'a to the borrow of the list.
+//
+// Safety: `ptr` is a live node that stays allocated for 'a, and no `&mut` to
+// its element exists during 'a.
+pub(crate) unsafe fn get_element_at_ptr<'a, T>(ptr: XorLink) -> &'a T {
+    unsafe { &(*node_at::<XorNode<T>>(ptr)).elem }
 }
 
-pub(crate) fn get_element_at_ptr_mut<'a, T>(ptr: XorLink) -> &'a mut T {
+// Frees the node and returns its element.
+//
+// Safety: `ptr` was allocated as an XorNode<T> and is no longer reachable
+// from any list, nothing refers to it afterwards.
+pub(crate) unsafe fn consume_element_at_ptr<T>(ptr: XorLink) -> T {
     unsafe {
-        let node = &mut *(ptr as *mut XorNode<T>);
-        &mut node.elem
+        let boxed_node = Box::from_raw(node_at::<XorNode<T>>(ptr));
+        boxed_node.elem
     }
 }
 
-pub(crate) fn point_a_to_b<T>(a: XorLink, b: XorLink) {
-    assert!(a != 0);
-    unsafe {
-        let a_node = &mut *(a as *mut XorNode<T>);
-
-        a_node.xor_pointer ^= b;
-        if b == 0 {
-            return;
-        }
-        let b_node = &mut *(b as *mut XorNode<T>);
-        b_node.xor_pointer ^= a;
-    }
+// Safety: `ptr` is a live node that stays allocated for 'a, and nothing else
+// refers to its element during 'a.
+pub(crate) unsafe fn get_element_at_ptr_mut<'a, T>(ptr: XorLink) -> &'a mut T {
+    unsafe { &mut (*node_at::<XorNode<T>>(ptr)).elem }
 }
 
-pub(crate) fn _length_from_a_to_b(a: XorLink, b: XorLink, prev_a: XorLink) -> usize {
+// `link` gives the link cell of a live node
+fn point_links_a_to_b<'a>(
+    a: XorLink,
+    b: XorLink,
+    link: impl Fn(XorLink) -> &'a Cell<usize>,
+) -> Result<(), XorListError> {
     if a == 0 {
-        return 0;
+        return Err(XorListError::NullLink);
     }
-    let mut prev_ptr = prev_a;
-    let mut curr_ptr = a;
-    let mut count = 1;
-
-    while curr_ptr != b {
-        let next_ptr = get_next_ptr::<()>(prev_ptr, curr_ptr);
-        prev_ptr = curr_ptr;
-        curr_ptr = next_ptr;
-        count += 1;
+    let a_link = link(a);
+    a_link.set(a_link.get() ^ b);
+    if b != 0 {
+        let b_link = link(b);
+        b_link.set(b_link.get() ^ a);
     }
-    count
+    Ok(())
+}
+
+pub(crate) fn point_a_to_b<T>(a: XorLink, b: XorLink) -> Result<(), XorListError> {
+    point_links_a_to_b(a, b, |ptr| unsafe { node_link::<T>(ptr) })
+}
+
+pub(crate) fn point_linked_a_to_b<N: XorLinked>(
+    a: XorLink,
+    b: XorLink,
+) -> Result<(), XorListError> {
+    point_links_a_to_b(a, b, |ptr| unsafe { (*node_at::<N>(ptr)).xor_link() })
 }
 
 pub struct XorIter<'a, T>
@@ -77,20 +147,20 @@ where
 {
     prev_ptr: usize,
     curr_ptr: usize,
-    _phantom_data: &'a std::marker::PhantomData<T>,
+    _phantom_data: core::marker::PhantomData<&'a XorLinkedList<T>>,
 }
 
-impl<T> XorIter<'_, T> {
-    fn new(list: &XorLinkedList<T>, reverse: bool) -> XorIter<'_, T> {
+impl<'a, T> XorIter<'a, T> {
+    fn new(list: &'a XorLinkedList<T>, reverse: bool) -> XorIter<'a, T> {
         XorIter {
             prev_ptr: 0,
             curr_ptr: if reverse { list.end } else { list.begin },
-            _phantom_data: &std::marker::PhantomData,
+            _phantom_data: core::marker::PhantomData,
         }
     }
 }
 
-impl<'a, T> std::iter::Iterator for XorIter<'a, T>
+impl<'a, T> core::iter::Iterator for XorIter<'a, T>
 where
     T: 'a,
 {
@@ -101,7 +171,8 @@ where
             return None;
         }
 
-        let element = get_element_at_ptr::<T>(self.curr_ptr);
+        // the list is borrowed for 'a, so its nodes outlive the returned reference
+        let element = unsafe { get_element_at_ptr::<T>(self.curr_ptr) };
 
         let next_ptr = get_next_ptr::<T>(self.prev_ptr, self.curr_ptr);
         self.prev_ptr = self.curr_ptr;
@@ -117,20 +188,20 @@ where
 {
     prev_ptr: usize,
     curr_ptr: usize,
-    _phantom_data: &'a std::marker::PhantomData<T>,
+    _phantom_data: core::marker::PhantomData<&'a mut XorLinkedList<T>>,
 }
 
-impl<T> XorIterMut<'_, T> {
-    fn new(list: &XorLinkedList<T>, reverse: bool) -> XorIterMut<'_, T> {
+impl<'a, T> XorIterMut<'a, T> {
+    fn new(list: &'a mut XorLinkedList<T>, reverse: bool) -> XorIterMut<'a, T> {
         XorIterMut {
             prev_ptr: 0,
             curr_ptr: if reverse { list.end } else { list.begin },
-            _phantom_data: &std::marker::PhantomData,
+            _phantom_data: core::marker::PhantomData,
         }
     }
 }
 
-impl<'a, T> std::iter::Iterator for XorIterMut<'a, T>
+impl<'a, T> core::iter::Iterator for XorIterMut<'a, T>
 where
     T: 'a,
 {
@@ -141,7 +212,8 @@ where
             return None;
         }
 
-        let element = get_element_at_ptr_mut::<T>(self.curr_ptr);
+        // the list is borrowed mutably for 'a and every node is visited once
+        let element = unsafe { get_element_at_ptr_mut::<T>(self.curr_ptr) };
         let next_ptr = get_next_ptr::<T>(self.prev_ptr, self.curr_ptr);
         self.prev_ptr = self.curr_ptr;
         self.curr_ptr = next_ptr;
@@ -150,14 +222,161 @@ where
     }
 }
 
-#[derive(Default)]
+// Opaque handle to a node of a XorLinkedList. A handle stays valid until its
+// node is removed; unlinking or inserting around a node in O(1) also needs the
+// handle of its predecessor, since an XOR node alone cannot name its neighbours.
+// Nothing ties a handle to its list at run time, so the methods taking handles
+// are unsafe; with `debug_checks` they panic on handles whose node is gone.
+pub struct NodeRef<T> {
+    ptr: XorLink,
+    #[cfg(feature = "debug_checks")]
+    generation: usize,
+    _phantom_data: core::marker::PhantomData<*const T>,
+}
+
+impl<T> NodeRef<T> {
+    fn new(ptr: XorLink) -> Self {
+        Self {
+            ptr,
+            #[cfg(feature = "debug_checks")]
+            generation: get_generation::<T>(ptr),
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+}
+
+// returned when a NodeRef outlived its node, only tracked with `debug_checks`
+#[cfg(feature = "debug_checks")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleHandle;
+
+#[cfg(feature = "debug_checks")]
+impl core::fmt::Display for StaleHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("stale NodeRef: its node was removed from the list")
+    }
+}
+
+#[cfg(feature = "debug_checks")]
+impl core::error::Error for StaleHandle {}
+
+// reported by the fallible list operations, the infallible ones panic with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XorListError {
+    // exactly one of begin and end is null
+    InconsistentEnds,
+    // a link was to be changed on the null node
+    NullLink,
+    // the allocator could not provide a node
+    AllocFailed,
+    // the list already holds the number of nodes it was limited to
+    CapacityExceeded { limit: usize },
+}
+
+impl core::fmt::Display for XorListError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            XorListError::InconsistentEnds => {
+                f.write_str("Invalid state: only one of begin and end is set")
+            }
+            XorListError::NullLink => f.write_str("Invalid state: relinking the null node"),
+            XorListError::AllocFailed => f.write_str("failed to allocate a list node"),
+            XorListError::CapacityExceeded { limit } => {
+                write!(f, "list is limited to {limit} nodes")
+            }
+        }
+    }
+}
+
+impl core::error::Error for XorListError {}
+
+pub(crate) fn expect_valid<R>(result: Result<R, XorListError>) -> R {
+    result.unwrap_or_else(|err| panic!("{err}"))
+}
+
+// structural damage found by XorLinkedList::validate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorruptionError {
+    // exactly one of begin and end is null
+    UnpairedEndpoints,
+    // the walk from begin never reaches a null link
+    Cycle,
+    // the walk from begin stops at a node other than end
+    DanglingEnd,
+    // the walk back from end visits a different node at this position
+    WalkMismatch { position: usize },
+    // the walk back from end does not stop at begin
+    DanglingBegin,
+    LengthMismatch { stored: usize, counted: usize },
+    // a link leads to a node the list does not own, only detected with `debug_checks`
+    UnownedNode { position: usize },
+}
+
+impl core::fmt::Display for CorruptionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CorruptionError::UnpairedEndpoints => f.write_str("only one of begin and end is set"),
+            CorruptionError::Cycle => f.write_str("the chain loops back on itself"),
+            CorruptionError::DanglingEnd => f.write_str("walking from begin does not end at end"),
+            CorruptionError::WalkMismatch { position } => write!(
+                f,
+                "forward and backward walks disagree at position {position}"
+            ),
+            CorruptionError::DanglingBegin => {
+                f.write_str("walking back from end does not end at begin")
+            }
+            CorruptionError::LengthMismatch { stored, counted } => write!(
+                f,
+                "stored length {stored} but the chain holds {counted} nodes"
+            ),
+            CorruptionError::UnownedNode { position } => {
+                write!(f, "node at position {position} is not owned by the list")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CorruptionError {}
+
+fn handle_ptr<T>(handle: Option<NodeRef<T>>) -> XorLink {
+    handle.map_or(0, |handle| handle.ptr)
+}
+
+impl<T> Clone for NodeRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NodeRef<T> {}
+
+impl<T> PartialEq for NodeRef<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<T> Eq for NodeRef<T> {}
+
+impl<T> core::fmt::Debug for NodeRef<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("NodeRef").field(&self.ptr).finish()
+    }
+}
+
 pub struct XorLinkedList<T> {
     //  allocator?
     pub(crate) begin: XorLink,
     pub(crate) end: XorLink,
-    phantom_data: std::marker::PhantomData<T>,
-    #[cfg(feature = "parallel_sized")]
+    phantom_data: core::marker::PhantomData<T>,
+    #[cfg(feature = "tracked_len")]
     pub(crate) length: usize,
+    // addresses of the nodes currently owned by the list
+    #[cfg(feature = "debug_checks")]
+    live: alloc::collections::BTreeSet<XorLink>,
+    // mutations since the last automatic validation
+    #[cfg(feature = "debug_checks_amortized")]
+    unvalidated: usize,
 }
 
 impl<T> XorLinkedList<T> {
@@ -165,16 +384,208 @@ impl<T> XorLinkedList<T> {
         Self {
             begin: 0,
             end: 0,
-            phantom_data: std::marker::PhantomData,
-            #[cfg(feature = "parallel_sized")]
+            phantom_data: core::marker::PhantomData,
+            #[cfg(feature = "tracked_len")]
             length: 0,
+            #[cfg(feature = "debug_checks")]
+            live: alloc::collections::BTreeSet::new(),
+            #[cfg(feature = "debug_checks_amortized")]
+            unvalidated: 0,
         }
     }
 
+    // moves all of `other` behind the end of this list in O(1)
     pub fn append(&mut self, other: &mut XorLinkedList<T>) {
-        while let Some(elem) = other.pop_front() {
-            self.push_back(elem);
+        if other.begin == 0 {
+            return;
+        }
+
+        if self.end == 0 {
+            // empty list
+            self.begin = other.begin;
+        } else {
+            expect_valid(point_a_to_b::<T>(self.end, other.begin));
+        }
+        self.end = other.end;
+        other.begin = 0;
+        other.end = 0;
+
+        #[cfg(feature = "tracked_len")]
+        {
+            self.length += other.length;
+            other.length = 0;
+        }
+        #[cfg(feature = "debug_checks")]
+        {
+            self.live.append(&mut other.live);
+            self.mutated();
+        }
+    }
+
+    // Keeps the elements for which `keep` returns true. A rejected node is
+    // unlinked and freed before its element is dropped, so a panic in `keep`
+    // or in T::drop leaves a valid list with the elements not yet removed.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut prev_ptr = 0;
+        let mut curr_ptr = self.begin;
+        while curr_ptr != 0 {
+            if keep(self.element(curr_ptr)) {
+                let next_ptr = get_next_ptr::<T>(prev_ptr, curr_ptr);
+                prev_ptr = curr_ptr;
+                curr_ptr = next_ptr;
+            } else {
+                let next_ptr = self.unlink_node(prev_ptr, curr_ptr);
+                drop(unsafe { self.consume_node(curr_ptr) });
+                curr_ptr = next_ptr;
+            }
+        }
+    }
+
+    // splits the list in two at the given index, walking `at` nodes from the front
+    pub fn split_off(&mut self, at: usize) -> XorLinkedList<T> {
+        let mut prev_ptr = 0;
+        let mut curr_ptr = self.begin;
+        for _ in 0..at {
+            assert!(curr_ptr != 0, "Cannot split off at a nonexistent index");
+            let next_ptr = get_next_ptr::<T>(prev_ptr, curr_ptr);
+            prev_ptr = curr_ptr;
+            curr_ptr = next_ptr;
+        }
+
+        let mut other = XorLinkedList::new();
+        if curr_ptr == 0 {
+            return other;
+        }
+
+        if prev_ptr != 0 {
+            expect_valid(point_a_to_b::<T>(prev_ptr, curr_ptr)); // cut the chain between the halves
+        } else {
+            self.begin = 0;
+        }
+        other.begin = curr_ptr;
+        other.end = self.end;
+        self.end = prev_ptr;
+
+        #[cfg(feature = "tracked_len")]
+        {
+            other.length = self.length - at;
+            self.length = at;
+        }
+        #[cfg(feature = "debug_checks")]
+        {
+            other.live = other.iter_ptrs().collect();
+            self.live.retain(|ptr| !other.live.contains(ptr));
+            self.mutated();
+            other.mutated();
+        }
+
+        other
+    }
+
+    #[cfg(feature = "debug_checks")]
+    fn iter_ptrs(&self) -> impl Iterator<Item = XorLink> + '_ {
+        let mut prev_ptr = 0;
+        let mut curr_ptr = self.begin;
+        core::iter::from_fn(move || {
+            if curr_ptr == 0 {
+                return None;
+            }
+            let ptr = curr_ptr;
+            curr_ptr = get_next_ptr::<T>(prev_ptr, curr_ptr);
+            prev_ptr = ptr;
+            Some(ptr)
+        })
+    }
+
+    // Walks the chain from both ends without trusting it. With `debug_checks`
+    // every address is looked up among the owned nodes before it is followed,
+    // without it a corrupted link can still lead outside of the list.
+    pub fn validate(&self) -> Result<(), CorruptionError> {
+        if (self.begin == 0) != (self.end == 0) {
+            return Err(CorruptionError::UnpairedEndpoints);
+        }
+
+        // Floyd: the tortoise takes one step for every two of the walk and only
+        // ever visits nodes that were already checked
+        let mut forward = Vec::new();
+        let (mut prev_ptr, mut curr_ptr) = (0, self.begin);
+        let (mut slow_prev, mut slow_curr) = (0, self.begin);
+        while curr_ptr != 0 {
+            self.check_owned(curr_ptr, forward.len())?;
+            forward.push(curr_ptr);
+            (prev_ptr, curr_ptr) = (curr_ptr, get_next_ptr::<T>(prev_ptr, curr_ptr));
+            if forward.len() % 2 == 0 {
+                (slow_prev, slow_curr) = (slow_curr, get_next_ptr::<T>(slow_prev, slow_curr));
+                if (slow_prev, slow_curr) == (prev_ptr, curr_ptr) {
+                    return Err(CorruptionError::Cycle);
+                }
+            }
+        }
+        if prev_ptr != self.end {
+            return Err(CorruptionError::DanglingEnd);
+        }
+
+        let (mut prev_ptr, mut curr_ptr) = (0, self.end);
+        for position in (0..forward.len()).rev() {
+            if curr_ptr != forward[position] {
+                return Err(CorruptionError::WalkMismatch { position });
+            }
+            (prev_ptr, curr_ptr) = (curr_ptr, get_next_ptr::<T>(prev_ptr, curr_ptr));
+        }
+        if curr_ptr != 0 {
+            return Err(CorruptionError::DanglingBegin);
+        }
+
+        #[cfg(feature = "tracked_len")]
+        if self.length != forward.len() {
+            return Err(CorruptionError::LengthMismatch {
+                stored: self.length,
+                counted: forward.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_owned(&self, ptr: XorLink, position: usize) -> Result<(), CorruptionError> {
+        #[cfg(feature = "debug_checks")]
+        if !self.live.contains(&ptr) {
+            return Err(CorruptionError::UnownedNode { position });
+        }
+        #[cfg(not(feature = "debug_checks"))]
+        let _ = (ptr, position);
+        Ok(())
+    }
+
+    // full validation after every mutation in debug builds, O(n) each unless
+    // `debug_checks_amortized` spreads it out on long lists
+    #[cfg(feature = "debug_checks")]
+    fn mutated(&mut self) {
+        if !cfg!(debug_assertions) {
+            return;
         }
+        #[cfg(feature = "debug_checks_amortized")]
+        {
+            self.unvalidated += 1;
+            if self.live.len() > VALIDATE_ALWAYS_LEN && self.unvalidated < self.live.len() {
+                return;
+            }
+            self.unvalidated = 0;
+        }
+        if let Err(err) = self.validate() {
+            panic!("corrupted XorLinkedList: {err}");
+        }
+    }
+
+    // `ptr` must be a node of this list
+    pub(crate) fn element(&self, ptr: XorLink) -> &T {
+        unsafe { get_element_at_ptr(ptr) }
+    }
+
+    pub(crate) fn element_mut(&mut self, ptr: XorLink) -> &mut T {
+        unsafe { get_element_at_ptr_mut(ptr) }
     }
 
     pub fn iter(&self) -> XorIter<'_, T> {
@@ -185,11 +596,11 @@ impl<T> XorLinkedList<T> {
         XorIter::new(self, true)
     }
 
-    pub fn iter_mut(&self) -> XorIterMut<'_, T> {
+    pub fn iter_mut(&mut self) -> XorIterMut<'_, T> {
         XorIterMut::new(self, false)
     }
 
-    pub fn iter_mut_rev(&self) -> XorIterMut<'_, T> {
+    pub fn iter_mut_rev(&mut self) -> XorIterMut<'_, T> {
         XorIterMut::new(self, true)
     }
 
@@ -198,11 +609,11 @@ impl<T> XorLinkedList<T> {
     }
 
     pub fn len(&self) -> usize {
-        #[cfg(feature = "parallel_sized")]
+        #[cfg(feature = "tracked_len")]
         {
             self.length
         }
-        #[cfg(not(feature = "parallel_sized"))]
+        #[cfg(not(feature = "tracked_len"))]
         {
             self.iter().count()
         }
@@ -213,93 +624,418 @@ impl<T> XorLinkedList<T> {
     }
 
     pub fn push_back_mut(&mut self, element: T) -> &mut T {
-        let node = Box::new(XorNode::new(element));
-        let ptr = Box::into_raw(node);
+        let ptr = self.push_back_ptr(element);
+        self.element_mut(ptr)
+    }
 
-        #[cfg(feature = "parallel_sized")]
-        {
-            self.length += 1;
+    pub fn push_front(&mut self, element: T) {
+        expect_valid(self.check_ends());
+        let ptr = self.alloc_node(element);
+        expect_valid(self.try_push_front_node(ptr));
+    }
+
+    // like push_back, but an allocation failure is returned instead of aborting
+    pub fn try_push_back(&mut self, element: T) -> Result<(), XorListError> {
+        self.check_ends()?;
+        let ptr = self.try_alloc_node(element)?;
+        self.try_push_back_node(ptr)
+    }
+
+    pub fn try_push_front(&mut self, element: T) -> Result<(), XorListError> {
+        self.check_ends()?;
+        let ptr = self.try_alloc_node(element)?;
+        self.try_push_front_node(ptr)
+    }
+
+    // stops at the first failure, elements pushed before it stay in the list
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), XorListError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for element in iter {
+            self.try_push_back(element)?;
         }
+        Ok(())
+    }
+
+    pub(crate) fn push_back_ptr(&mut self, element: T) -> XorLink {
+        expect_valid(self.check_ends());
+        let ptr = self.alloc_node(element);
+        self.push_back_node(ptr);
+        ptr
+    }
+
+    fn check_ends(&self) -> Result<(), XorListError> {
+        if (self.begin == 0) != (self.end == 0) {
+            return Err(XorListError::InconsistentEnds);
+        }
+        Ok(())
+    }
 
+    // aborts on allocation failure, as Box::new does
+    fn alloc_node(&mut self, element: T) -> XorLink {
+        match self.try_alloc_node(element) {
+            Ok(ptr) => ptr,
+            Err(XorListError::AllocFailed) => {
+                alloc::alloc::handle_alloc_error(alloc::alloc::Layout::new::<XorNode<T>>())
+            }
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    // the node layout is never zero sized, and a node allocated here can be
+    // freed through Box::from_raw
+    fn try_alloc_node(&mut self, element: T) -> Result<XorLink, XorListError> {
+        let layout = alloc::alloc::Layout::new::<XorNode<T>>();
+        let node = unsafe { alloc::alloc::alloc(layout) } as *mut XorNode<T>;
+        if node.is_null() {
+            return Err(XorListError::AllocFailed);
+        }
+        unsafe { node.write(XorNode::new(element)) };
+        let ptr = link_of(node);
+        #[cfg(feature = "debug_checks")]
+        self.live.insert(ptr);
+        Ok(ptr)
+    }
+
+    // frees a node that is no longer linked into the list
+    //
+    // Safety: `ptr` was a node of this list and has been unlinked from it
+    pub(crate) unsafe fn consume_node(&mut self, ptr: XorLink) -> T {
+        #[cfg(feature = "debug_checks")]
+        self.live.remove(&ptr);
+        unsafe { consume_element_at_ptr::<T>(ptr) }
+    }
+
+    // links an allocated node with a cleared xor_pointer behind the current end
+    pub(crate) fn push_back_node(&mut self, ptr: XorLink) {
+        expect_valid(self.try_push_back_node(ptr));
+    }
+
+    fn try_push_back_node(&mut self, ptr: XorLink) -> Result<(), XorListError> {
+        self.check_ends()?;
         if self.begin == 0 {
             // empty list
-            self.begin = ptr as usize;
-            self.end = ptr as usize;
-            return get_element_at_ptr_mut(ptr as usize);
+            self.begin = ptr;
+        } else {
+            point_a_to_b::<T>(self.end, ptr)?;
+        }
+        self.end = ptr;
+
+        #[cfg(feature = "tracked_len")]
+        {
+            self.length += 1;
         }
+        #[cfg(feature = "debug_checks")]
+        self.mutated();
+        Ok(())
+    }
 
+    fn try_push_front_node(&mut self, ptr: XorLink) -> Result<(), XorListError> {
+        self.check_ends()?;
         if self.end == 0 {
-            panic!("Invalid state: begin is set but end is not");
+            // empty list
+            self.end = ptr;
+        } else {
+            point_a_to_b::<T>(self.begin, ptr)?;
         }
-        point_a_to_b::<T>(self.end, ptr as usize);
-        self.end = ptr as usize;
+        self.begin = ptr;
 
-        get_element_at_ptr_mut(ptr as usize)
+        #[cfg(feature = "tracked_len")]
+        {
+            self.length += 1;
+        }
+        #[cfg(feature = "debug_checks")]
+        self.mutated();
+        Ok(())
     }
 
-    pub fn pop_back(&mut self) -> Option<T> {
-        if self.end == 0 {
-            return None;
+    // relinks the front node behind the end of `other` without reallocating it
+    #[cfg(feature = "parallel")]
+    pub(crate) fn move_front_to(&mut self, other: &mut XorLinkedList<T>) {
+        let ptr = self.begin;
+        assert!(ptr != 0);
+        #[cfg(feature = "debug_checks")]
+        {
+            self.live.remove(&ptr);
+            other.live.insert(ptr);
+        }
+        self.unlink_node(0, ptr);
+        other.push_back_node(ptr);
+    }
+
+    // detaches `ptr`, whose predecessor is `prev`, without freeing it and
+    // returns the node that used to follow it
+    pub(crate) fn unlink_node(&mut self, prev: XorLink, ptr: XorLink) -> XorLink {
+        expect_valid(self.try_unlink_node(prev, ptr))
+    }
+
+    fn try_unlink_node(&mut self, prev: XorLink, ptr: XorLink) -> Result<XorLink, XorListError> {
+        if ptr == 0 {
+            return Err(XorListError::NullLink);
         }
+        let next = get_next_ptr::<T>(prev, ptr);
 
-        if self.begin == 0 {
-            panic!("Invalid state: end is set but begin is not");
+        point_a_to_b::<T>(ptr, prev)?;
+        point_a_to_b::<T>(ptr, next)?;
+        if prev != 0 {
+            point_a_to_b::<T>(prev, next)?;
+        } else {
+            self.begin = next;
+        }
+        if next == 0 {
+            self.end = prev;
         }
 
-        #[cfg(feature = "parallel_sized")]
+        #[cfg(feature = "tracked_len")]
         {
             self.length -= 1;
         }
+        #[cfg(feature = "debug_checks")]
+        self.mutated();
+        Ok(next)
+    }
 
-        if self.begin == self.end {
-            // only one element
-            let elem = consume_element_at_ptr::<T>(self.end);
-            self.begin = 0;
-            self.end = 0;
-            return Some(elem);
+    pub fn push_back_handle(&mut self, element: T) -> NodeRef<T> {
+        NodeRef::new(self.push_back_ptr(element))
+    }
+
+    pub fn front_handle(&self) -> Option<NodeRef<T>> {
+        (self.begin != 0).then(|| NodeRef::new(self.begin))
+    }
+
+    pub fn back_handle(&self) -> Option<NodeRef<T>> {
+        (self.end != 0).then(|| NodeRef::new(self.end))
+    }
+
+    /// The node following `curr`, where `prev` is the node before it.
+    ///
+    /// # Safety
+    ///
+    /// `curr` and `prev` must be handles of nodes still in this list, and
+    /// `prev` the one right before `curr` (`None` when `curr` is the front).
+    pub unsafe fn next_handle(
+        &self,
+        prev: Option<NodeRef<T>>,
+        curr: NodeRef<T>,
+    ) -> Option<NodeRef<T>> {
+        self.assert_live(prev);
+        self.assert_live(Some(curr));
+        let prev = handle_ptr(prev);
+        debug_assert!(
+            self.is_adjacent(prev, curr.ptr),
+            "prev is not the predecessor of curr"
+        );
+        match get_next_ptr::<T>(prev, curr.ptr) {
+            0 => None,
+            next => Some(NodeRef::new(next)),
         }
+    }
 
-        let old_end = self.end;
-        let prev_ptr = get_next_ptr::<T>(0, self.end);
-        point_a_to_b::<T>(prev_ptr, self.end); // clean current end
-        self.end = prev_ptr;
+    /// # Safety
+    ///
+    /// `handle` must be the handle of a node still in this list.
+    pub unsafe fn get(&self, handle: NodeRef<T>) -> &T {
+        self.assert_live(Some(handle));
+        self.element(handle.ptr)
+    }
 
-        Some(consume_element_at_ptr::<T>(old_end))
+    /// # Safety
+    ///
+    /// `handle` must be the handle of a node still in this list.
+    pub unsafe fn get_mut(&mut self, handle: NodeRef<T>) -> &mut T {
+        self.assert_live(Some(handle));
+        self.element_mut(handle.ptr)
     }
 
-    pub fn pop_front(&mut self) -> Option<T> {
-        if self.begin == 0 {
-            return None;
+    #[cfg(feature = "debug_checks")]
+    pub fn check_handle(&self, handle: NodeRef<T>) -> Result<(), StaleHandle> {
+        // the address has to be checked first, reading a freed node is UB
+        if self.live.contains(&handle.ptr) && get_generation::<T>(handle.ptr) == handle.generation {
+            Ok(())
+        } else {
+            Err(StaleHandle)
         }
+    }
 
-        if self.end == 0 {
-            panic!("Invalid state: begin is set but end is not");
+    #[cfg(feature = "debug_checks")]
+    pub fn try_get(&self, handle: NodeRef<T>) -> Result<&T, StaleHandle> {
+        self.check_handle(handle)?;
+        Ok(self.element(handle.ptr))
+    }
+
+    #[cfg(feature = "debug_checks")]
+    pub fn try_get_mut(&mut self, handle: NodeRef<T>) -> Result<&mut T, StaleHandle> {
+        self.check_handle(handle)?;
+        Ok(self.element_mut(handle.ptr))
+    }
+
+    fn assert_live(&self, handle: Option<NodeRef<T>>) {
+        #[cfg(feature = "debug_checks")]
+        if let Some(handle) = handle
+            && let Err(err) = self.check_handle(handle)
+        {
+            panic!("{err}");
         }
+        #[cfg(not(feature = "debug_checks"))]
+        let _ = handle;
+    }
+
+    /// Inserts `element` right after `curr`, where `prev` is the node before it.
+    ///
+    /// # Safety
+    ///
+    /// As for [`XorLinkedList::next_handle`].
+    pub unsafe fn insert_after(
+        &mut self,
+        prev: Option<NodeRef<T>>,
+        curr: NodeRef<T>,
+        element: T,
+    ) -> NodeRef<T> {
+        self.assert_live(prev);
+        self.assert_live(Some(curr));
+        let prev = handle_ptr(prev);
+        debug_assert!(
+            self.is_adjacent(prev, curr.ptr),
+            "prev is not the predecessor of curr"
+        );
+        let next = get_next_ptr::<T>(prev, curr.ptr);
+        let ptr = self.alloc_node(element);
 
-        #[cfg(feature = "parallel_sized")]
+        #[cfg(feature = "tracked_len")]
         {
-            self.length -= 1;
+            self.length += 1;
+        }
+
+        expect_valid(
+            point_a_to_b::<T>(curr.ptr, next) // detach curr from next
+                .and_then(|()| point_a_to_b::<T>(curr.ptr, ptr))
+                .and_then(|()| point_a_to_b::<T>(ptr, next)),
+        );
+        if next == 0 {
+            self.end = ptr;
+        }
+
+        #[cfg(feature = "debug_checks")]
+        self.mutated();
+        NodeRef::new(ptr)
+    }
+
+    /// Removes `handle`, where `prev` is the node before it.
+    ///
+    /// # Safety
+    ///
+    /// As for [`XorLinkedList::next_handle`], with `handle` in place of `curr`.
+    /// `handle` must not be used again afterwards.
+    pub unsafe fn remove_between(&mut self, prev: Option<NodeRef<T>>, handle: NodeRef<T>) -> T {
+        self.assert_live(prev);
+        self.assert_live(Some(handle));
+        let prev = handle_ptr(prev);
+        debug_assert!(
+            self.is_adjacent(prev, handle.ptr),
+            "prev is not the predecessor of handle"
+        );
+        self.unlink_node(prev, handle.ptr);
+        unsafe { self.consume_node(handle.ptr) }
+    }
+
+    // O(n) walk, only used to validate handles in debug builds
+    fn is_adjacent(&self, prev: XorLink, curr: XorLink) -> bool {
+        let mut prev_ptr = 0;
+        let mut curr_ptr = self.begin;
+        while curr_ptr != 0 {
+            if curr_ptr == curr {
+                return prev_ptr == prev;
+            }
+            let next_ptr = get_next_ptr::<T>(prev_ptr, curr_ptr);
+            prev_ptr = curr_ptr;
+            curr_ptr = next_ptr;
+        }
+        false
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        expect_valid(self.try_pop_back())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        expect_valid(self.try_pop_front())
+    }
+
+    pub fn try_pop_back(&mut self) -> Result<Option<T>, XorListError> {
+        self.check_ends()?;
+        if self.end == 0 {
+            return Ok(None);
         }
 
-        if self.begin == self.end {
+        let old_end = self.end;
+        let prev_ptr = get_next_ptr::<T>(0, old_end);
+        if prev_ptr == 0 {
             // only one element
-            let elem = consume_element_at_ptr::<T>(self.begin);
             self.begin = 0;
-            self.end = 0;
-            return Some(elem);
+        } else {
+            point_a_to_b::<T>(prev_ptr, old_end)?; // clean current end
+        }
+        self.end = prev_ptr;
+
+        #[cfg(feature = "tracked_len")]
+        {
+            self.length -= 1;
+        }
+        let elem = unsafe { self.consume_node(old_end) };
+        #[cfg(feature = "debug_checks")]
+        self.mutated();
+        Ok(Some(elem))
+    }
+
+    pub fn try_pop_front(&mut self) -> Result<Option<T>, XorListError> {
+        self.check_ends()?;
+        if self.begin == 0 {
+            return Ok(None);
         }
 
         let old_begin = self.begin;
-        let next_ptr = get_next_ptr::<T>(0, self.begin);
-        point_a_to_b::<T>(next_ptr, self.begin); // clean current begin
+        let next_ptr = get_next_ptr::<T>(0, old_begin);
+        if next_ptr == 0 {
+            // only one element
+            self.end = 0;
+        } else {
+            point_a_to_b::<T>(next_ptr, old_begin)?; // clean current begin
+        }
         self.begin = next_ptr;
 
-        Some(consume_element_at_ptr::<T>(old_begin))
+        #[cfg(feature = "tracked_len")]
+        {
+            self.length -= 1;
+        }
+        let elem = unsafe { self.consume_node(old_begin) };
+        #[cfg(feature = "debug_checks")]
+        self.mutated();
+        Ok(Some(elem))
+    }
+}
+
+impl<T> Default for XorLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl<T> Drop for XorLinkedList<T> {
+    // a broken list is leaked rather than panicking in drop
     fn drop(&mut self) {
-        while self.pop_back().is_some() {}
+        // if dropping an element panics, the guard frees the remaining nodes
+        // while unwinding; a second panic from there aborts
+        struct DropGuard<'a, T>(&'a mut XorLinkedList<T>);
+
+        impl<T> Drop for DropGuard<'_, T> {
+            fn drop(&mut self) {
+                while let Ok(Some(_)) = self.0.try_pop_back() {}
+            }
+        }
+
+        let guard = DropGuard(self);
+        while let Ok(Some(_)) = guard.0.try_pop_back() {}
+        core::mem::forget(guard);
     }
 }
@@ -0,0 +1,154 @@
+use crate::xor_linked_list::{XorLink, XorLinkedList, get_next_ptr};
+use std::boxed::Box;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub struct XorLruCache<K, V> {
+    // front is the least recently used entry, back the most recently used one
+    list: XorLinkedList<(K, V)>,
+    // key -> (node, predecessor of node), an XOR node can only be unlinked
+    // when one of its neighbours is known
+    map: HashMap<K, (XorLink, XorLink)>,
+    capacity: usize,
+    on_evict: Option<Box<dyn FnMut(K, V) + Send>>,
+}
+
+impl<K: Hash + Eq + Clone, V> XorLruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            list: XorLinkedList::new(),
+            map: HashMap::with_capacity(capacity),
+            capacity,
+            on_evict: None,
+        }
+    }
+
+    pub fn with_eviction_callback(
+        capacity: usize,
+        on_evict: impl FnMut(K, V) + Send + 'static,
+    ) -> Self {
+        let mut cache = Self::new(capacity);
+        cache.on_evict = Some(Box::new(on_evict));
+        cache
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    // looks up an entry without changing its recency
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let &(node, _) = self.map.get(key)?;
+        Some(&self.list.element(node).1)
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let node = self.touch(key)?;
+        Some(&self.list.element(node).1)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let node = self.touch(key)?;
+        Some(&mut self.list.element_mut(node).1)
+    }
+
+    // inserts or updates `key` as the most recently used entry and returns the
+    // previous value, evicting the least recently used entry when full
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(node) = self.touch(&key) {
+            let entry = self.list.element_mut(node);
+            return Some(std::mem::replace(&mut entry.1, value));
+        }
+
+        if self.capacity == 0 {
+            self.evict(key, value);
+            return None;
+        }
+        if self.map.len() >= self.capacity {
+            self.evict_lru();
+        }
+
+        // if inserting panics, the node stays in the list without an entry
+        // until pop_lru reaches it
+        let prev = self.list.end;
+        let node = self.list.push_back_ptr((key.clone(), value));
+        self.map.insert(key, (node, prev));
+        None
+    }
+
+    // Hash and Eq are user code that may panic, so every entry is looked up
+    // before the list changes, and no entry refers to an unlinked node
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let node = self.list.begin;
+        if node == 0 {
+            return None;
+        }
+
+        let removed = self.map.remove_entry(&self.list.element(node).0);
+        let next = get_next_ptr::<(K, V)>(0, node);
+        if next != 0
+            && let Some(entry) = self.map.get_mut(&self.list.element(next).0)
+        {
+            entry.1 = 0;
+        }
+        self.list.unlink_node(0, node);
+
+        let entry = unsafe { self.list.consume_node(node) };
+        drop(removed);
+        Some(entry)
+    }
+
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.map.len() > self.capacity {
+            self.evict_lru();
+        }
+    }
+
+    fn evict_lru(&mut self) {
+        if let Some((key, value)) = self.pop_lru() {
+            self.evict(key, value);
+        }
+    }
+
+    fn evict(&mut self, key: K, value: V) {
+        if let Some(on_evict) = self.on_evict.as_mut() {
+            on_evict(key, value);
+        }
+    }
+
+    // moves the node of `key` to the back of the list
+    fn touch(&mut self, key: &K) -> Option<XorLink> {
+        let &(node, prev) = self.map.get(key)?;
+        if node == self.list.end {
+            return Some(node);
+        }
+
+        // both entries are looked up before the list changes, as in pop_lru
+        let next = get_next_ptr::<(K, V)>(prev, node);
+        let next_key = &self.list.element(next).0;
+        let [Some(entry), next_entry] = self.map.get_disjoint_mut([key, next_key]) else {
+            return None;
+        };
+        let new_prev = self.list.end;
+        self.list.unlink_node(prev, node);
+        self.list.push_back_node(node);
+        entry.1 = new_prev;
+        if let Some(next_entry) = next_entry {
+            next_entry.1 = prev;
+        }
+        Some(node)
+    }
+}
@@ -0,0 +1,431 @@
+use alloc::alloc::Layout;
+use alloc::vec::Vec;
+
+use crate::xor_linked_list::{
+    XorLink, XorListError, XorNode, expect_valid, get_element_at_ptr, get_element_at_ptr_mut,
+    get_next_ptr, link_of, node_at, point_a_to_b,
+};
+
+const MAX_LEVEL: usize = 32;
+const DEFAULT_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// xorshift64* so that level assignment is reproducible from a seed
+pub struct LevelGenerator {
+    state: u64,
+}
+
+impl LevelGenerator {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on a zero state
+        Self {
+            state: if seed == 0 { DEFAULT_SEED } else { seed },
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // every extra level is taken with probability 1/2
+    pub fn next_level(&mut self) -> usize {
+        (self.next_u64().trailing_ones() as usize + 1).min(MAX_LEVEL)
+    }
+}
+
+impl Default for LevelGenerator {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+// Level 0 is a plain XorNode chain. A node of height h additionally carries, for
+// every level l in 1..h, the XOR link of that level and a descent link
+// next_{l-1} ^ next_l, which lets a search drop from level l to level l - 1
+// without knowing the predecessor on the lower level. These 2 * (h - 1) links
+// follow the XorNode in the same allocation, so a node costs one allocation
+// and a single word for its height on top of a list node.
+struct SkipEntry<K, V> {
+    key: K,
+    value: V,
+    height: usize,
+}
+
+impl<K, V> SkipEntry<K, V> {
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+// the layout of a node of `height` and the offset of its tower, which does not
+// depend on the height
+fn node_layout<K, V>(height: usize) -> (Layout, usize) {
+    let tower = Layout::array::<XorLink>(2 * (height - 1)).unwrap();
+    let (layout, offset) = Layout::new::<XorNode<SkipEntry<K, V>>>()
+        .extend(tower)
+        .unwrap();
+    (layout.pad_to_align(), offset)
+}
+
+fn alloc_node<K, V>(key: K, value: V, height: usize) -> XorLink {
+    let (layout, offset) = node_layout::<K, V>(height);
+    let node = unsafe { alloc::alloc::alloc(layout) };
+    if node.is_null() {
+        alloc::alloc::handle_alloc_error(layout);
+    }
+    unsafe {
+        let entry = SkipEntry { key, value, height };
+        node.cast::<XorNode<SkipEntry<K, V>>>()
+            .write(XorNode::new(entry));
+        node.add(offset)
+            .cast::<XorLink>()
+            .write_bytes(0, 2 * (height - 1));
+    }
+    link_of(node)
+}
+
+// Safety: as for consume_element_at_ptr, `ptr` was made by alloc_node
+unsafe fn free_node<K, V>(ptr: XorLink) -> SkipEntry<K, V> {
+    let node = node_at::<XorNode<SkipEntry<K, V>>>(ptr);
+    let entry = unsafe { node.read() }.into_element();
+    let (layout, _) = node_layout::<K, V>(entry.height);
+    unsafe { alloc::alloc::dealloc(node.cast(), layout) };
+    entry
+}
+
+// link `index` of the tower, `ptr` must be a node of height above index / 2 + 1
+fn tower<K, V>(ptr: XorLink, index: usize) -> *mut XorLink {
+    let (_, offset) = node_layout::<K, V>(1);
+    node_at::<u8>(ptr)
+        .wrapping_add(offset)
+        .cast::<XorLink>()
+        .wrapping_add(index)
+}
+
+// Safety: as for get_element_at_ptr(_mut). The link helpers below only keep
+// the entry for the statement that adjusts a tower, list methods go through
+// XorSkipList::entry(_mut) instead.
+unsafe fn entry<'a, K, V>(ptr: XorLink) -> &'a SkipEntry<K, V> {
+    unsafe { get_element_at_ptr::<SkipEntry<K, V>>(ptr) }
+}
+
+unsafe fn entry_mut<'a, K, V>(ptr: XorLink) -> &'a mut SkipEntry<K, V> {
+    unsafe { get_element_at_ptr_mut::<SkipEntry<K, V>>(ptr) }
+}
+
+fn get_next_ptr_at<K, V>(level: usize, prev_ptr: XorLink, curr: XorLink) -> XorLink {
+    if level == 0 {
+        return get_next_ptr::<SkipEntry<K, V>>(prev_ptr, curr);
+    }
+    prev_ptr ^ unsafe { *tower::<K, V>(curr, 2 * (level - 1)) }
+}
+
+fn point_a_to_b_at<K, V>(level: usize, a: XorLink, b: XorLink) -> Result<(), XorListError> {
+    if level == 0 {
+        return point_a_to_b::<SkipEntry<K, V>>(a, b);
+    }
+    if a == 0 {
+        return Err(XorListError::NullLink);
+    }
+    let index = 2 * (level - 1);
+    unsafe { *tower::<K, V>(a, index) ^= b };
+    if b != 0 {
+        unsafe { *tower::<K, V>(b, index) ^= a };
+    }
+    Ok(())
+}
+
+// next_{level-1} ^ next_level, only defined for 1 <= level < height
+fn get_descent<K, V>(ptr: XorLink, level: usize) -> XorLink {
+    unsafe { *tower::<K, V>(ptr, 2 * (level - 1) + 1) }
+}
+
+// the successor of `ptr` on `level` changed by `delta`, which touches the
+// descent links of `level` and `level + 1`
+fn retarget<K, V>(ptr: XorLink, level: usize, delta: XorLink) {
+    let height = unsafe { entry::<K, V>(ptr) }.height();
+    if level >= 1 {
+        unsafe { *tower::<K, V>(ptr, 2 * (level - 1) + 1) ^= delta };
+    }
+    if level + 1 < height {
+        unsafe { *tower::<K, V>(ptr, 2 * level + 1) ^= delta };
+    }
+}
+
+pub struct XorSkipIter<'a, K, V>
+where
+    K: 'a,
+    V: 'a,
+{
+    prev_ptr: XorLink,
+    curr_ptr: XorLink,
+    _phantom_data: core::marker::PhantomData<&'a XorSkipList<K, V>>,
+}
+
+impl<'a, K, V> XorSkipIter<'a, K, V> {
+    fn new(list: &'a XorSkipList<K, V>, reverse: bool) -> XorSkipIter<'a, K, V> {
+        XorSkipIter {
+            prev_ptr: 0,
+            curr_ptr: if reverse {
+                list.tail
+            } else {
+                list.heads.first().copied().unwrap_or(0)
+            },
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for XorSkipIter<'a, K, V>
+where
+    K: 'a,
+    V: 'a,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.curr_ptr == 0 {
+            return None;
+        }
+
+        // the list is borrowed for 'a
+        let node = unsafe { entry::<K, V>(self.curr_ptr) };
+
+        let next_ptr = get_next_ptr_at::<K, V>(0, self.prev_ptr, self.curr_ptr);
+        self.prev_ptr = self.curr_ptr;
+        self.curr_ptr = next_ptr;
+
+        Some((&node.key, &node.value))
+    }
+}
+
+pub struct XorSkipList<K, V> {
+    // first node of every level, heads[0] is the front of the list
+    heads: Vec<XorLink>,
+    // last node of level 0
+    tail: XorLink,
+    length: usize,
+    levels: LevelGenerator,
+    phantom_data: core::marker::PhantomData<(K, V)>,
+}
+
+impl<K: Ord, V> XorSkipList<K, V> {
+    pub fn new() -> Self {
+        Self::with_level_generator(LevelGenerator::default())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_level_generator(LevelGenerator::new(seed))
+    }
+
+    pub fn with_level_generator(levels: LevelGenerator) -> Self {
+        Self {
+            heads: Vec::new(),
+            tail: 0,
+            length: 0,
+            levels,
+            phantom_data: core::marker::PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn height(&self) -> usize {
+        self.heads.len()
+    }
+
+    // `ptr` must be a node of this list
+    fn entry(&self, ptr: XorLink) -> &SkipEntry<K, V> {
+        unsafe { entry::<K, V>(ptr) }
+    }
+
+    fn entry_mut(&mut self, ptr: XorLink) -> &mut SkipEntry<K, V> {
+        unsafe { entry_mut::<K, V>(ptr) }
+    }
+
+    pub fn iter(&self) -> XorSkipIter<'_, K, V> {
+        XorSkipIter::new(self, false)
+    }
+
+    pub fn iter_rev(&self) -> XorSkipIter<'_, K, V> {
+        XorSkipIter::new(self, true)
+    }
+
+    // Walks from the top level down. On every level `visit` receives the last
+    // node whose key is smaller than `key` (0 for the head) and its successor.
+    // Returns the successor on level 0, the first node not smaller than `key`.
+    fn walk(&self, key: &K, mut visit: impl FnMut(usize, XorLink, XorLink)) -> XorLink {
+        let mut curr: XorLink = 0;
+        let mut next: XorLink = 0;
+        for level in (0..self.heads.len()).rev() {
+            next = if curr == 0 {
+                self.heads[level]
+            } else {
+                get_descent::<K, V>(curr, level + 1) ^ next
+            };
+            while next != 0 && self.entry(next).key < *key {
+                let after = get_next_ptr_at::<K, V>(level, curr, next);
+                curr = next;
+                next = after;
+            }
+            visit(level, curr, next);
+        }
+        next
+    }
+
+    fn find(&self, key: &K) -> XorLink {
+        let found = self.walk(key, |_, _, _| {});
+        if found != 0 && self.entry(found).key == *key {
+            found
+        } else {
+            0
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key) != 0
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self.find(key) {
+            0 => None,
+            ptr => Some(&self.entry(ptr).value),
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.find(key) {
+            0 => None,
+            ptr => Some(&mut self.entry_mut(ptr).value),
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut preds = [0; MAX_LEVEL];
+        let mut succs = [0; MAX_LEVEL];
+        let found = self.walk(&key, |level, pred, succ| {
+            preds[level] = pred;
+            succs[level] = succ;
+        });
+        if found != 0 && self.entry(found).key == key {
+            return Some(core::mem::replace(&mut self.entry_mut(found).value, value));
+        }
+
+        let height = self.levels.next_level();
+        // new levels start out empty, preds and succs are already 0 there
+        while self.heads.len() < height {
+            self.heads.push(0);
+        }
+
+        let ptr = alloc_node(key, value, height);
+
+        for level in 0..height {
+            let pred = preds[level];
+            let succ = succs[level];
+            if pred != 0 {
+                expect_valid(point_a_to_b_at::<K, V>(level, pred, succ)); // detach pred from succ
+                expect_valid(point_a_to_b_at::<K, V>(level, pred, ptr));
+                retarget::<K, V>(pred, level, succ ^ ptr);
+            } else {
+                self.heads[level] = ptr;
+            }
+            expect_valid(point_a_to_b_at::<K, V>(level, ptr, succ));
+            if level == 0 && succ == 0 {
+                self.tail = ptr;
+            }
+            if level >= 1 {
+                let descent = tower::<K, V>(ptr, 2 * (level - 1) + 1);
+                unsafe { *descent = succs[level - 1] ^ succ };
+            }
+        }
+
+        self.length += 1;
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let mut preds = [0; MAX_LEVEL];
+        let found = self.walk(key, |level, pred, _| {
+            preds[level] = pred;
+        });
+        if found == 0 || self.entry(found).key != *key {
+            return None;
+        }
+
+        // the removed node is the successor of preds[level] on all of its levels
+        let height = self.entry(found).height();
+        for (level, &pred) in preds.iter().enumerate().take(height) {
+            let succ = get_next_ptr_at::<K, V>(level, pred, found);
+            expect_valid(point_a_to_b_at::<K, V>(level, found, pred));
+            expect_valid(point_a_to_b_at::<K, V>(level, found, succ));
+            if pred != 0 {
+                expect_valid(point_a_to_b_at::<K, V>(level, pred, succ));
+                retarget::<K, V>(pred, level, found ^ succ);
+            } else {
+                self.heads[level] = succ;
+            }
+            if level == 0 && succ == 0 {
+                self.tail = pred;
+            }
+        }
+
+        while self.heads.last() == Some(&0) {
+            self.heads.pop();
+        }
+        self.length -= 1;
+
+        // found was unlinked from all of its levels above
+        let removed = unsafe { free_node::<K, V>(found) };
+        Some(removed.value)
+    }
+}
+
+impl<K: Ord, V> Default for XorSkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// level 0 from `curr_ptr` on, every node is freed before its entry is dropped
+struct FreeNodes<K, V> {
+    prev_ptr: XorLink,
+    curr_ptr: XorLink,
+    phantom_data: core::marker::PhantomData<(K, V)>,
+}
+
+impl<K, V> FreeNodes<K, V> {
+    fn free_all(&mut self) {
+        while self.curr_ptr != 0 {
+            let ptr = self.curr_ptr;
+            self.curr_ptr = get_next_ptr_at::<K, V>(0, self.prev_ptr, ptr);
+            self.prev_ptr = ptr;
+            drop(unsafe { free_node::<K, V>(ptr) });
+        }
+    }
+}
+
+// if dropping a key or value panics, the rest is freed while unwinding
+impl<K, V> Drop for FreeNodes<K, V> {
+    fn drop(&mut self) {
+        self.free_all();
+    }
+}
+
+impl<K, V> Drop for XorSkipList<K, V> {
+    fn drop(&mut self) {
+        let mut nodes = FreeNodes::<K, V> {
+            prev_ptr: 0,
+            curr_ptr: self.heads.first().copied().unwrap_or(0),
+            phantom_data: core::marker::PhantomData,
+        };
+        nodes.free_all();
+    }
+}
@@ -0,0 +1,413 @@
+use crate::xor_linked_list::{
+    XorLink, XorLinkedList, get_element_at_ptr, get_element_at_ptr_mut, get_next_ptr,
+};
+use core::ops::{Bound, RangeBounds};
+
+// takes `steps` steps away from `prev_ptr`, returning the final (prev, curr)
+pub(crate) fn walk<T>(
+    mut prev_ptr: XorLink,
+    mut curr_ptr: XorLink,
+    steps: usize,
+) -> (XorLink, XorLink) {
+    for _ in 0..steps {
+        let next_ptr = get_next_ptr::<T>(prev_ptr, curr_ptr);
+        prev_ptr = curr_ptr;
+        curr_ptr = next_ptr;
+    }
+    (prev_ptr, curr_ptr)
+}
+
+// [start, end] of a list, together with the nodes just outside of it
+#[derive(Clone, Copy)]
+pub(crate) struct XorWindow {
+    pub(crate) prev_start: XorLink,
+    pub(crate) start: XorLink,
+    pub(crate) end: XorLink,
+    pub(crate) prev_end: XorLink,
+    pub(crate) length: usize,
+    // position of start in the whole list
+    pub(crate) offset: usize,
+}
+
+impl XorWindow {
+    pub(crate) fn with_length<T>(list: &XorLinkedList<T>, length: usize) -> XorWindow {
+        XorWindow {
+            prev_start: 0,
+            start: list.begin,
+            end: list.end,
+            prev_end: 0,
+            length,
+            offset: 0,
+        }
+    }
+
+    pub(crate) fn split_at<T>(self, mid: usize) -> (XorWindow, XorWindow) {
+        self.split_at_from::<T>(mid, None)
+    }
+
+    // `checkpoint` is a known (position, prev, curr) at or before the split
+    // point, used when it is closer than both ends of the window
+    pub(crate) fn split_at_from<T>(
+        self,
+        mid: usize,
+        checkpoint: Option<(usize, XorLink, XorLink)>,
+    ) -> (XorWindow, XorWindow) {
+        let length = self.length;
+        assert!(mid <= length);
+        // first half [0, mid)
+        let first_prev_start = self.prev_start;
+        let first_start = self.start;
+
+        // we need pointer before and at mid, walked to from the closest of
+        // start, end and the checkpoint
+        let target = self.offset + mid;
+        let checkpoint =
+            checkpoint.filter(|&(position, _, _)| target - position < mid.min(length - mid));
+
+        let (prev_mid, mid_ptr) = if let Some((position, prev_ptr, curr_ptr)) = checkpoint {
+            walk::<T>(prev_ptr, curr_ptr, target - position)
+        } else if mid <= length - mid {
+            walk::<T>(self.prev_start, self.start, mid)
+        } else {
+            // walking back from end, the node after the current one is mid
+            let (mid_ptr, prev_mid) = walk::<T>(self.prev_end, self.end, length - mid);
+            (prev_mid, mid_ptr)
+        };
+
+        let first_half = XorWindow {
+            prev_start: first_prev_start,
+            start: first_start,
+            end: prev_mid,
+            prev_end: mid_ptr,
+            length: mid,
+            offset: self.offset,
+        };
+
+        // second half [mid, length)
+        let second_end = self.end;
+        let second_prev_end = self.prev_end;
+
+        let second_half = XorWindow {
+            prev_start: prev_mid,
+            start: mid_ptr,
+            end: second_end,
+            prev_end: second_prev_end,
+            length: length - mid,
+            offset: target,
+        };
+
+        (first_half, second_half)
+    }
+
+    // the node at `index`, walked to from the nearer end
+    fn get<T>(&self, index: usize) -> Option<XorLink> {
+        if index >= self.length {
+            return None;
+        }
+        let ptr = if index < self.length - index {
+            walk::<T>(self.prev_start, self.start, index).1
+        } else {
+            walk::<T>(self.prev_end, self.end, self.length - 1 - index).1
+        };
+        Some(ptr)
+    }
+
+    pub(crate) fn next<T>(&mut self) -> Option<XorLink> {
+        let curr_ptr = self.advance::<T>()?;
+        self.length -= 1;
+        Some(curr_ptr)
+    }
+
+    // steps start forward without relying on a known length
+    pub(crate) fn advance<T>(&mut self) -> Option<XorLink> {
+        if self.start == 0 || self.start == self.prev_end {
+            return None;
+        }
+
+        let curr_ptr = self.start;
+
+        let next_ptr = get_next_ptr::<T>(self.prev_start, self.start);
+        self.prev_start = self.start;
+        self.start = next_ptr;
+        self.offset += 1;
+
+        Some(curr_ptr)
+    }
+
+    pub(crate) fn next_back<T>(&mut self) -> Option<XorLink> {
+        if self.end == 0 || self.end == self.prev_start {
+            return None;
+        }
+
+        let curr_ptr = self.end;
+
+        let next_ptr = get_next_ptr::<T>(self.prev_end, self.end);
+        self.prev_end = self.end;
+        self.end = next_ptr;
+        self.length -= 1;
+
+        Some(curr_ptr)
+    }
+}
+
+// sequential iterator over a window, shared by slices and the parallel producers;
+// whoever creates one from a window borrows the list it covers for 'a
+pub struct XorSliceIter<'a, T> {
+    window: XorWindow,
+    _phantom_data: core::marker::PhantomData<&'a XorLinkedList<T>>,
+}
+
+impl<'a, T> XorSliceIter<'a, T> {
+    pub(crate) fn new(window: XorWindow) -> XorSliceIter<'a, T> {
+        XorSliceIter {
+            window,
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for XorSliceIter<'a, T> {
+    fn len(&self) -> usize {
+        self.window.length
+    }
+}
+
+impl<'a, T> Iterator for XorSliceIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.window
+            .next::<T>()
+            .map(|ptr| unsafe { get_element_at_ptr::<T>(ptr) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.window.length, Some(self.window.length))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for XorSliceIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.window
+            .next_back::<T>()
+            .map(|ptr| unsafe { get_element_at_ptr::<T>(ptr) })
+    }
+}
+
+// every window is disjoint from the others, so each element is handed out once
+pub struct XorSliceIterMut<'a, T> {
+    window: XorWindow,
+    _phantom_data: core::marker::PhantomData<&'a mut XorLinkedList<T>>,
+}
+
+impl<'a, T> XorSliceIterMut<'a, T> {
+    pub(crate) fn new(window: XorWindow) -> XorSliceIterMut<'a, T> {
+        XorSliceIterMut {
+            window,
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for XorSliceIterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.window.length
+    }
+}
+
+impl<'a, T> Iterator for XorSliceIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.window
+            .next::<T>()
+            .map(|ptr| unsafe { get_element_at_ptr_mut::<T>(ptr) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.window.length, Some(self.window.length))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for XorSliceIterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.window
+            .next_back::<T>()
+            .map(|ptr| unsafe { get_element_at_ptr_mut::<T>(ptr) })
+    }
+}
+
+// Borrowed run of consecutive list nodes. It knows its length and the nodes
+// just outside of it, so it can be iterated and split from either end.
+pub struct XorSlice<'a, T> {
+    window: XorWindow,
+    _phantom_data: core::marker::PhantomData<&'a XorLinkedList<T>>,
+}
+
+impl<'a, T> XorSlice<'a, T> {
+    pub(crate) fn new(window: XorWindow) -> XorSlice<'a, T> {
+        XorSlice {
+            window,
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.window.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.length == 0
+    }
+
+    pub fn iter(&self) -> XorSliceIter<'a, T> {
+        XorSliceIter::new(self.window)
+    }
+
+    // walks min(mid, len - mid) nodes
+    pub fn split_at(self, mid: usize) -> (XorSlice<'a, T>, XorSlice<'a, T>) {
+        assert!(mid <= self.len(), "mid > len");
+        let (first_half, second_half) = self.window.split_at::<T>(mid);
+        (XorSlice::new(first_half), XorSlice::new(second_half))
+    }
+
+    pub fn first(&self) -> Option<&'a T> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&'a T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        self.window
+            .get::<T>(index)
+            .map(|ptr| unsafe { get_element_at_ptr::<T>(ptr) })
+    }
+}
+
+impl<'a, T> Clone for XorSlice<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for XorSlice<'a, T> {}
+
+impl<'a, T> IntoIterator for XorSlice<'a, T> {
+    type Item = &'a T;
+    type IntoIter = XorSliceIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct XorSliceMut<'a, T> {
+    window: XorWindow,
+    _phantom_data: core::marker::PhantomData<&'a mut XorLinkedList<T>>,
+}
+
+impl<'a, T> XorSliceMut<'a, T> {
+    fn new(window: XorWindow) -> XorSliceMut<'a, T> {
+        XorSliceMut {
+            window,
+            _phantom_data: core::marker::PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.window.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.length == 0
+    }
+
+    pub fn iter(&self) -> XorSliceIter<'_, T> {
+        XorSliceIter::new(self.window)
+    }
+
+    pub fn iter_mut(&mut self) -> XorSliceIterMut<'_, T> {
+        XorSliceIterMut::new(self.window)
+    }
+
+    // both halves can be mutated independently, so the slice is consumed
+    pub fn split_at(self, mid: usize) -> (XorSliceMut<'a, T>, XorSliceMut<'a, T>) {
+        assert!(mid <= self.len(), "mid > len");
+        let (first_half, second_half) = self.window.split_at::<T>(mid);
+        (XorSliceMut::new(first_half), XorSliceMut::new(second_half))
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.window
+            .get::<T>(index)
+            .map(|ptr| unsafe { get_element_at_ptr::<T>(ptr) })
+    }
+
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len().checked_sub(1)?)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.window
+            .get::<T>(index)
+            .map(|ptr| unsafe { get_element_at_ptr_mut::<T>(ptr) })
+    }
+}
+
+impl<'a, T> IntoIterator for XorSliceMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = XorSliceIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        XorSliceIterMut::new(self.window)
+    }
+}
+
+impl<T> XorLinkedList<T> {
+    // counts the list unless the length is stored, then walks to both bounds
+    fn window<R: RangeBounds<usize>>(&self, range: R) -> XorWindow {
+        let length = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).unwrap_or_else(|| {
+                panic!("range start after usize::MAX out of bounds for list of length {length}")
+            }),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).unwrap_or_else(|| {
+                panic!("range end at usize::MAX out of bounds for list of length {length}")
+            }),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => length,
+        };
+        assert!(
+            start <= end && end <= length,
+            "range {start}..{end} out of bounds for list of length {length}"
+        );
+        let (_, rest) = XorWindow::with_length(self, length).split_at::<T>(start);
+        rest.split_at::<T>(end - start).0
+    }
+
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> XorSlice<'_, T> {
+        XorSlice::new(self.window(range))
+    }
+
+    pub fn slice_mut<R: RangeBounds<usize>>(&mut self, range: R) -> XorSliceMut<'_, T> {
+        XorSliceMut::new(self.window(range))
+    }
+}
@@ -0,0 +1,34 @@
+#![no_std]
+
+use xor_linked_list::{XorLinkedList, XorSkipList};
+
+pub fn sum_of_squares(n: u32) -> u32 {
+    let mut list = XorLinkedList::new();
+    for i in 0..n {
+        list.push_back(i);
+    }
+    for x in list.iter_mut() {
+        *x *= *x;
+    }
+    list.iter().sum()
+}
+
+pub fn drain_reversed(n: u32) -> u32 {
+    let mut list = XorLinkedList::new();
+    for i in 0..n {
+        list.push_front(i);
+    }
+    let mut last = 0;
+    while let Some(x) = list.pop_front() {
+        last = x;
+    }
+    last
+}
+
+pub fn skip_list_lookup(key: u32) -> Option<u32> {
+    let mut map = XorSkipList::new();
+    for i in 0..16 {
+        map.insert(i, i * 2);
+    }
+    map.get(&key).copied()
+}
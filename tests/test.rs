@@ -1,16 +1,21 @@
 #[cfg(test)]
 mod tests {
-    use xor_linked_list::XorLinkedList;
+    use xor_linked_list::{CorruptionError, NodeRef, XorLinkedList, XorListError};
 
     fn get_count() -> usize {
-        #[cfg(feature = "parallel_sized")]
-        {
-            3
-        }
-        #[cfg(not(feature = "parallel_sized"))]
-        {
-            2
-        }
+        #[cfg(feature = "tracked_len")]
+        let count = 3;
+        #[cfg(not(feature = "tracked_len"))]
+        let count = 2;
+        // the live node set
+        #[cfg(feature = "debug_checks")]
+        let count = count
+            + std::mem::size_of::<std::collections::BTreeSet<usize>>()
+                / std::mem::size_of::<usize>();
+        // the count of unvalidated mutations
+        #[cfg(feature = "debug_checks_amortized")]
+        let count = count + 1;
+        count
     }
 
     #[test]
@@ -166,6 +171,212 @@ mod tests {
         assert!(list2.is_empty());
     }
 
+    #[test]
+    fn test_append_empty() {
+        let mut list1 = XorLinkedList::<i32>::new();
+        let mut list2 = XorLinkedList::<i32>::new();
+        list1.append(&mut list2);
+        assert!(list1.is_empty());
+
+        list2.push_back(1);
+        list1.append(&mut list2);
+        assert!(list2.is_empty());
+        assert_eq!(list1.len(), 1);
+
+        list1.append(&mut list2);
+        list1.push_back(2);
+        assert_eq!(list1.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(list1.iter_rev().copied().collect::<Vec<_>>(), vec![2, 1]);
+
+        list2.push_back(3);
+        assert_eq!(list2.len(), 1);
+        assert_eq!(list2.pop_front(), Some(3));
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut list = XorLinkedList::<i32>::new();
+        for i in 0..5 {
+            list.push_back(i);
+        }
+
+        let mut tail = list.split_off(2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(list.iter_rev().copied().collect::<Vec<_>>(), vec![1, 0]);
+        assert_eq!(tail.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(tail.iter_rev().copied().collect::<Vec<_>>(), vec![4, 3, 2]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(tail.len(), 3);
+
+        let empty = tail.split_off(3);
+        assert!(empty.is_empty());
+        assert_eq!(tail.len(), 3);
+
+        let all = tail.split_off(0);
+        assert!(tail.is_empty());
+        assert_eq!(all.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_len_tracking() {
+        fn check(list: &XorLinkedList<i32>, expected: usize) {
+            assert_eq!(list.len(), expected);
+            assert_eq!(list.iter().count(), expected);
+            assert_eq!(list.is_empty(), expected == 0);
+        }
+
+        let mut list = XorLinkedList::<i32>::new();
+        check(&list, 0);
+        let first = list.push_back_handle(1);
+        *list.push_back_mut(3) += 1;
+        check(&list, 2);
+
+        let second = unsafe { list.insert_after(None, first, 2) };
+        check(&list, 3);
+        assert_eq!(unsafe { list.remove_between(Some(first), second) }, 2);
+        check(&list, 2);
+
+        let mut other = XorLinkedList::new();
+        other.push_back(5);
+        other.push_back(6);
+        list.append(&mut other);
+        check(&list, 4);
+        check(&other, 0);
+
+        let tail = list.split_off(1);
+        check(&list, 1);
+        check(&tail, 3);
+
+        list.pop_back();
+        list.pop_front();
+        check(&list, 0);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut list = XorLinkedList::new();
+        for i in 0..20 {
+            list.push_back(i);
+        }
+        list.retain(|&x| x % 3 != 0);
+        assert_eq!(list.len(), 13);
+        assert_eq!(list.validate(), Ok(()));
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 4, 5, 7, 8, 10, 11, 13, 14, 16, 17, 19]
+        );
+
+        list.retain(|&x| x > 17);
+        assert_eq!(list.iter_rev().copied().collect::<Vec<_>>(), vec![19]);
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert_eq!(list.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut list = XorLinkedList::<i32>::new();
+        assert_eq!(list.validate(), Ok(()));
+        let first = list.push_back_handle(0);
+        assert_eq!(list.validate(), Ok(()));
+        for i in 1..200 {
+            list.push_back(i);
+        }
+        unsafe {
+            let second = list.insert_after(None, first, -1);
+            list.remove_between(Some(first), second);
+        }
+        let mut tail = list.split_off(150);
+        assert_eq!(list.validate(), Ok(()));
+        assert_eq!(tail.validate(), Ok(()));
+
+        tail.pop_front();
+        tail.pop_back();
+        list.append(&mut tail);
+        assert_eq!(list.validate(), Ok(()));
+        assert_eq!(tail.validate(), Ok(()));
+        while list.pop_front().is_some() {
+            assert_eq!(list.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_corruption_error_display() {
+        assert_eq!(
+            CorruptionError::LengthMismatch {
+                stored: 3,
+                counted: 2
+            }
+            .to_string(),
+            "stored length 3 but the chain holds 2 nodes"
+        );
+        assert_eq!(
+            CorruptionError::WalkMismatch { position: 4 }.to_string(),
+            "forward and backward walks disagree at position 4"
+        );
+    }
+
+    #[test]
+    fn test_push_front() {
+        let mut list = XorLinkedList::<i32>::new();
+        list.push_front(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(list.try_push_front(0), Ok(()));
+        assert_eq!(list.try_push_back(4), Ok(()));
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            list.iter_rev().copied().collect::<Vec<_>>(),
+            vec![4, 3, 2, 1, 0]
+        );
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_try_pop() {
+        let mut list = XorLinkedList::<i32>::new();
+        assert_eq!(list.try_pop_back(), Ok(None));
+        assert_eq!(list.try_pop_front(), Ok(None));
+        list.push_back(1);
+        list.push_back(2);
+        assert_eq!(list.try_pop_front(), Ok(Some(1)));
+        assert_eq!(list.try_pop_back(), Ok(Some(2)));
+        assert_eq!(list.try_pop_back(), Ok(None));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_try_extend() {
+        let mut list = XorLinkedList::new();
+        assert_eq!(list.try_extend(vec![1, 2, 3]), Ok(()));
+        assert_eq!(list.try_extend(Vec::new()), Ok(()));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_list_error_display() {
+        assert_eq!(
+            XorListError::InconsistentEnds.to_string(),
+            "Invalid state: only one of begin and end is set"
+        );
+        assert_eq!(
+            XorListError::AllocFailed.to_string(),
+            "failed to allocate a list node"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "nonexistent index")]
+    fn test_split_off_out_of_bounds() {
+        let mut list = XorLinkedList::<i32>::new();
+        list.push_back(1);
+        list.split_off(2);
+    }
+
     #[test]
     fn test_push_back_mut() {
         let mut list = XorLinkedList::<i32>::new();
@@ -292,4 +503,117 @@ mod tests {
             std::mem::size_of::<usize>() * get_count()
         );
     }
+
+    #[test]
+    fn test_handles() {
+        let mut list = XorLinkedList::<i32>::new();
+        let a = list.push_back_handle(1);
+        let b = list.push_back_handle(2);
+        assert_eq!(list.front_handle(), Some(a));
+        assert_eq!(list.back_handle(), Some(b));
+        // a and b stay in the list
+        unsafe {
+            assert_eq!(list.next_handle(None, a), Some(b));
+            assert_eq!(list.next_handle(Some(a), b), None);
+
+            *list.get_mut(b) += 10;
+            assert_eq!(*list.get(a), 1);
+            assert_eq!(*list.get(b), 12);
+        }
+    }
+
+    #[test]
+    fn test_insert_after() {
+        let mut list = XorLinkedList::<i32>::new();
+        let a = list.push_back_handle(1);
+        let c = list.push_back_handle(3);
+
+        let b = unsafe { list.insert_after(Some(a), c, 4) };
+        let d = unsafe { list.insert_after(None, a, 2) };
+        assert_eq!(list.back_handle(), Some(b));
+        assert_eq!(unsafe { list.next_handle(Some(a), d) }, Some(c));
+        assert_eq!(list.len(), 4);
+
+        let forward: Vec<i32> = list.iter().copied().collect();
+        assert_eq!(forward, vec![1, 2, 3, 4]);
+        let backward: Vec<i32> = list.iter_rev().copied().collect();
+        assert_eq!(backward, vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_remove_between() {
+        let mut list = XorLinkedList::<i32>::new();
+        let handles: Vec<NodeRef<i32>> = (0..5).map(|i| list.push_back_handle(i)).collect();
+
+        // every handle is removed once, with the predecessor it has at that time
+        unsafe {
+            assert_eq!(2, list.remove_between(Some(handles[1]), handles[2]));
+            assert_eq!(0, list.remove_between(None, handles[0]));
+            assert_eq!(4, list.remove_between(Some(handles[3]), handles[4]));
+        }
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.front_handle(), Some(handles[1]));
+        assert_eq!(list.back_handle(), Some(handles[3]));
+
+        let forward: Vec<i32> = list.iter().copied().collect();
+        assert_eq!(forward, vec![1, 3]);
+        let backward: Vec<i32> = list.iter_rev().copied().collect();
+        assert_eq!(backward, vec![3, 1]);
+
+        unsafe {
+            assert_eq!(1, list.remove_between(None, handles[1]));
+            assert_eq!(3, list.remove_between(None, handles[3]));
+        }
+        assert!(list.is_empty());
+        assert_eq!(list.back_handle(), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "prev is not the predecessor")]
+    fn test_remove_between_not_adjacent() {
+        let mut list = XorLinkedList::<i32>::new();
+        let a = list.push_back_handle(1);
+        let _ = list.push_back_handle(2);
+        let c = list.push_back_handle(3);
+        // breaks the contract on purpose, the debug check catches it first
+        unsafe { list.remove_between(Some(a), c) };
+    }
+
+    #[test]
+    #[cfg(feature = "debug_checks")]
+    fn test_stale_handle() {
+        use xor_linked_list::StaleHandle;
+
+        let mut list = XorLinkedList::<i32>::new();
+        let a = list.push_back_handle(1);
+        let b = list.push_back_handle(2);
+        assert_eq!(Ok(()), list.check_handle(a));
+        assert_eq!(Ok(&2), list.try_get(b));
+
+        assert_eq!(Some(2), list.pop_back());
+        assert_eq!(Err(StaleHandle), list.check_handle(b));
+        assert_eq!(Err(StaleHandle), list.try_get_mut(b));
+
+        // a new node may reuse the freed address, the generation tells them apart
+        let c = list.push_back_handle(3);
+        assert_eq!(Err(StaleHandle), list.try_get(b));
+        assert_eq!(Ok(&3), list.try_get(c));
+
+        let other = XorLinkedList::<i32>::new();
+        assert_eq!(Err(StaleHandle), other.check_handle(a));
+    }
+
+    #[test]
+    #[cfg(feature = "debug_checks")]
+    #[should_panic(expected = "stale NodeRef")]
+    fn test_stale_handle_panics() {
+        let mut list = XorLinkedList::<i32>::new();
+        let a = list.push_back_handle(1);
+        // the stale access is caught by `debug_checks` before it touches memory
+        unsafe {
+            list.remove_between(None, a);
+            list.get(a);
+        }
+    }
 }
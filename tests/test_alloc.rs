@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use xor_linked_list::{XorLinkedList, XorListError};
+
+    thread_local! {
+        static FAIL_ALLOCATIONS: Cell<bool> = const { Cell::new(false) };
+    }
+
+    // refuses every allocation of the current thread while FAIL_ALLOCATIONS is set
+    struct FailingAllocator;
+
+    unsafe impl GlobalAlloc for FailingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            if FAIL_ALLOCATIONS.with(Cell::get) {
+                return std::ptr::null_mut();
+            }
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: FailingAllocator = FailingAllocator;
+
+    fn without_memory<R>(f: impl FnOnce() -> R) -> R {
+        FAIL_ALLOCATIONS.with(|fail| fail.set(true));
+        let result = f();
+        FAIL_ALLOCATIONS.with(|fail| fail.set(false));
+        result
+    }
+
+    #[test]
+    fn test_try_push_alloc_failure() {
+        let mut list = XorLinkedList::new();
+        list.try_push_back(1).unwrap();
+
+        assert_eq!(
+            without_memory(|| list.try_push_back(2)),
+            Err(XorListError::AllocFailed)
+        );
+        assert_eq!(
+            without_memory(|| list.try_push_front(0)),
+            Err(XorListError::AllocFailed)
+        );
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1]);
+
+        list.try_push_back(2).unwrap();
+        list.try_push_front(0).unwrap();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+    }
+}
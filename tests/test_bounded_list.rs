@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use xor_linked_list::{BoundedXorList, XorLinkedList, XorListError};
+
+    #[test]
+    fn test_capacity_limit() {
+        let mut list = XorLinkedList::<i32>::with_capacity_limit(3);
+        assert_eq!(list.capacity_limit(), 3);
+
+        assert_eq!(
+            list.try_extend(0..5),
+            Err(XorListError::CapacityExceeded { limit: 3 })
+        );
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(
+            list.try_push_front(-1),
+            Err(XorListError::CapacityExceeded { limit: 3 })
+        );
+
+        list.pop_front();
+        assert_eq!(list.try_push_front(-1), Ok(()));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![-1, 1, 2]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.validate(), Ok(()));
+
+        let mut inner = list.into_inner();
+        inner.push_back(3);
+        assert_eq!(inner.len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "list is limited to 1 nodes")]
+    fn test_capacity_limit_push_back() {
+        let mut list = XorLinkedList::with_capacity_limit(1);
+        list.push_back(1);
+        list.push_back(2);
+    }
+
+    #[test]
+    fn test_zero_limit() {
+        let mut list = BoundedXorList::new(0);
+        assert_eq!(
+            list.try_push_back(1),
+            Err(XorListError::CapacityExceeded { limit: 0 })
+        );
+        assert!(list.is_empty());
+        assert_eq!(list.pop_back(), None);
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use xor_linked_list::{IntrusiveXorList, XorLinked};
+
+    struct Task {
+        id: u32,
+        link: Cell<usize>,
+        linked: Cell<bool>,
+    }
+
+    impl Task {
+        fn new(id: u32) -> Self {
+            Self {
+                id,
+                link: Cell::new(0),
+                linked: Cell::new(false),
+            }
+        }
+    }
+
+    unsafe impl XorLinked for Task {
+        fn xor_link(&self) -> &Cell<usize> {
+            &self.link
+        }
+
+        fn linked(&self) -> &Cell<bool> {
+            &self.linked
+        }
+    }
+
+    fn ids(list: &IntrusiveXorList<'_, Task>) -> Vec<u32> {
+        list.iter().map(|task| task.id).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let list = IntrusiveXorList::<Task>::new();
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+        assert!(list.front().is_none());
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let tasks: Vec<Task> = (0..4).map(Task::new).collect();
+        let mut list = IntrusiveXorList::new();
+        list.push_back(&tasks[1]);
+        list.push_back(&tasks[2]);
+        list.push_front(&tasks[0]);
+        list.push_back(&tasks[3]);
+
+        assert_eq!(ids(&list), vec![0, 1, 2, 3]);
+        assert_eq!(
+            list.iter_rev().map(|task| task.id).collect::<Vec<_>>(),
+            vec![3, 2, 1, 0]
+        );
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.front().map(|task| task.id), Some(0));
+        assert_eq!(list.back().map(|task| task.id), Some(3));
+
+        assert_eq!(list.pop_front().map(|task| task.id), Some(0));
+        assert_eq!(list.pop_back().map(|task| task.id), Some(3));
+        assert_eq!(ids(&list), vec![1, 2]);
+        assert_eq!(tasks[0].link.get(), 0);
+        assert_eq!(tasks[3].link.get(), 0);
+
+        assert_eq!(list.pop_back().map(|task| task.id), Some(2));
+        assert_eq!(list.pop_back().map(|task| task.id), Some(1));
+        assert!(list.pop_back().is_none());
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_relink_after_drop() {
+        let tasks: Vec<Task> = (0..3).map(Task::new).collect();
+        {
+            let mut list = IntrusiveXorList::new();
+            for task in &tasks {
+                list.push_back(task);
+            }
+        }
+        assert!(tasks.iter().all(|task| task.link.get() == 0));
+
+        let mut list = IntrusiveXorList::new();
+        for task in tasks.iter().rev() {
+            list.push_back(task);
+        }
+        assert_eq!(ids(&list), vec![2, 1, 0]);
+        list.clear();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "already linked")]
+    fn test_double_link() {
+        let tasks: Vec<Task> = (0..2).map(Task::new).collect();
+        let mut list = IntrusiveXorList::new();
+        list.push_back(&tasks[0]);
+        list.push_back(&tasks[1]);
+        list.push_back(&tasks[0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "already linked")]
+    fn test_double_link_single_item() {
+        // the only item of a list has a zero link, like an unlinked one
+        let task = Task::new(0);
+        let mut first = IntrusiveXorList::new();
+        first.push_back(&task);
+        let mut second = IntrusiveXorList::new();
+        second.push_front(&task);
+    }
+
+    #[test]
+    fn test_relink_single_item() {
+        let task = Task::new(7);
+        let mut first = IntrusiveXorList::new();
+        first.push_back(&task);
+        assert!(task.linked.get());
+        assert_eq!(first.pop_front().map(|task| task.id), Some(7));
+        assert!(!task.linked.get());
+
+        let mut second = IntrusiveXorList::new();
+        second.push_back(&task);
+        assert_eq!(ids(&second), vec![7]);
+        drop(second);
+        assert!(!task.linked.get());
+    }
+
+    #[test]
+    fn test_push_clears_stale_link() {
+        let tasks: Vec<Task> = (0..3).map(Task::new).collect();
+        for task in &tasks {
+            task.link.set(0xdead_beef);
+        }
+        let mut list = IntrusiveXorList::new();
+        list.push_back(&tasks[1]);
+        list.push_back(&tasks[2]);
+        list.push_front(&tasks[0]);
+        assert_eq!(ids(&list), vec![0, 1, 2]);
+        assert_eq!(
+            list.iter_rev().map(|task| task.id).collect::<Vec<_>>(),
+            vec![2, 1, 0]
+        );
+    }
+}
@@ -0,0 +1,228 @@
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::cell::Cell;
+    use std::hash::{Hash, Hasher};
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::sync::{Arc, Mutex};
+    use xor_linked_list::XorLruCache;
+
+    thread_local! {
+        static PANICKING_KEY: Cell<Option<u32>> = const { Cell::new(None) };
+    }
+
+    // hashing panics for the key stored in PANICKING_KEY
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Key(u32);
+
+    impl Hash for Key {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            if PANICKING_KEY.get() == Some(self.0) {
+                panic!("hashing key {}", self.0);
+            }
+            self.0.hash(state);
+        }
+    }
+
+    fn hash_panics(
+        cache: &mut XorLruCache<Key, u32>,
+        key: u32,
+        f: impl FnOnce(&mut XorLruCache<Key, u32>),
+    ) {
+        PANICKING_KEY.set(Some(key));
+        let result = catch_unwind(AssertUnwindSafe(|| f(cache)));
+        PANICKING_KEY.set(None);
+        assert!(result.is_err());
+    }
+
+    fn drain(cache: &mut XorLruCache<Key, u32>) -> Vec<u32> {
+        std::iter::from_fn(|| cache.pop_lru().map(|(key, _)| key.0)).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let cache = XorLruCache::<i32, i32>::new(2);
+        assert!(cache.is_empty());
+        assert_eq!(cache.capacity(), 2);
+        assert_eq!(cache.peek(&1), None);
+    }
+
+    #[test]
+    fn test_put_get() {
+        let mut cache = XorLruCache::new(2);
+        assert_eq!(None, cache.put(1, "one"));
+        assert_eq!(None, cache.put(2, "two"));
+        assert_eq!(Some(&"one"), cache.get(&1));
+        assert_eq!(Some(&"two"), cache.get(&2));
+        assert_eq!(None, cache.get(&3));
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(Some("one"), cache.put(1, "uno"));
+        assert_eq!(Some(&"uno"), cache.peek(&1));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = XorLruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.get(&1);
+        cache.put(3, 30);
+
+        assert!(cache.contains(&1));
+        assert!(!cache.contains(&2));
+        assert!(cache.contains(&3));
+    }
+
+    #[test]
+    fn test_peek_does_not_touch() {
+        let mut cache = XorLruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        assert_eq!(Some(&10), cache.peek(&1));
+        cache.put(3, 30);
+
+        assert!(!cache.contains(&1));
+        assert!(cache.contains(&2));
+    }
+
+    #[test]
+    fn test_pop_lru_order() {
+        let mut cache = XorLruCache::new(4);
+        for i in 0..4 {
+            cache.put(i, i * 10);
+        }
+        cache.get(&0);
+        cache.get(&2);
+        *cache.get_mut(&1).unwrap() += 1;
+
+        assert_eq!(Some((3, 30)), cache.pop_lru());
+        assert_eq!(Some((0, 0)), cache.pop_lru());
+        assert_eq!(Some((2, 20)), cache.pop_lru());
+        assert_eq!(Some((1, 11)), cache.pop_lru());
+        assert_eq!(None, cache.pop_lru());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut cache = XorLruCache::new(4);
+        for i in 0..4 {
+            cache.put(i, i);
+        }
+        cache.resize(2);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(&2));
+        assert!(cache.contains(&3));
+
+        cache.resize(3);
+        cache.put(4, 4);
+        assert_eq!(cache.len(), 3);
+        assert!(cache.contains(&2));
+    }
+
+    #[test]
+    fn test_eviction_callback() {
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&evicted);
+        let mut cache =
+            XorLruCache::with_eviction_callback(2, move |k, v| sink.lock().unwrap().push((k, v)));
+
+        cache.put(1, 'a');
+        cache.put(2, 'b');
+        cache.put(3, 'c');
+        cache.get(&2);
+        cache.put(4, 'd');
+        cache.resize(1);
+        // pop_lru hands the entry back instead of reporting it
+        assert_eq!(Some((4, 'd')), cache.pop_lru());
+
+        assert_eq!(*evicted.lock().unwrap(), vec![(1, 'a'), (3, 'c'), (2, 'b')]);
+    }
+
+    #[test]
+    fn test_eviction_callback_send() {
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&evicted);
+        let mut cache =
+            XorLruCache::with_eviction_callback(1, move |k, v| sink.lock().unwrap().push((k, v)));
+        cache.put(1, "one");
+
+        std::thread::spawn(move || {
+            cache.put(2, "two");
+        })
+        .join()
+        .unwrap();
+        assert_eq!(*evicted.lock().unwrap(), vec![(1, "one")]);
+    }
+
+    #[test]
+    fn test_against_model() {
+        let mut cache = XorLruCache::new(8);
+        let mut model: Vec<(u32, u32)> = Vec::new();
+        for step in 0..2_000u32 {
+            let key = (step * 31 + step / 7) % 13;
+            if step % 4 == 0 {
+                let expected = model.iter().position(|&(k, _)| k == key).map(|i| {
+                    let entry = model.remove(i);
+                    model.push(entry);
+                    entry.1
+                });
+                assert_eq!(expected.as_ref(), cache.get(&key));
+            } else {
+                if let Some(i) = model.iter().position(|&(k, _)| k == key) {
+                    model.remove(i);
+                } else if model.len() == 8 {
+                    model.remove(0);
+                }
+                model.push((key, step));
+                cache.put(key, step);
+            }
+            assert_eq!(cache.len(), model.len());
+        }
+
+        while let Some(entry) = cache.pop_lru() {
+            assert_eq!(entry, model.remove(0));
+        }
+        assert!(model.is_empty());
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut cache = XorLruCache::new(0);
+        assert_eq!(None, cache.put(1, String::from("one")));
+        assert!(cache.is_empty());
+        assert_eq!(None, cache.get(&1));
+    }
+
+    #[test]
+    fn test_touch_hash_panics() {
+        let mut cache = XorLruCache::new(4);
+        for i in 0..4 {
+            cache.put(Key(i), i);
+        }
+        // get(1) moves the node behind 3 and has to fix up the entry of 2
+        hash_panics(&mut cache, 2, |cache| {
+            cache.get(&Key(1));
+        });
+        assert_eq!(cache.get(&Key(1)), Some(&1));
+        assert_eq!(cache.get(&Key(2)), Some(&2));
+        cache.put(Key(4), 4);
+        assert_eq!(drain(&mut cache), vec![3, 1, 2, 4]);
+    }
+
+    #[test]
+    fn test_pop_lru_hash_panics() {
+        let mut cache = XorLruCache::new(4);
+        for i in 0..4 {
+            cache.put(Key(i), i);
+        }
+        // evicting 0 has to fix up the entry of 1
+        hash_panics(&mut cache, 1, |cache| {
+            cache.put(Key(4), 4);
+        });
+        assert_eq!(cache.get(&Key(1)), Some(&1));
+        assert_eq!(cache.get(&Key(0)), None);
+        assert_eq!(drain(&mut cache), vec![0, 2, 3, 1]);
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use std::rc::Rc;
+    use xor_linked_list::{XorLinkedList, XorSkipList};
+
+    // counts its drops and panics in drop when `panics` is set
+    struct PanicOnDrop {
+        value: i32,
+        panics: bool,
+        drops: Rc<Cell<usize>>,
+    }
+
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+            if self.panics {
+                panic!("PanicOnDrop {}", self.value);
+            }
+        }
+    }
+
+    fn create_list(
+        size: i32,
+        panicking: &[i32],
+        drops: &Rc<Cell<usize>>,
+    ) -> XorLinkedList<PanicOnDrop> {
+        let mut list = XorLinkedList::new();
+        for value in 0..size {
+            list.push_back(PanicOnDrop {
+                value,
+                panics: panicking.contains(&value),
+                drops: drops.clone(),
+            });
+        }
+        list
+    }
+
+    fn values(list: &XorLinkedList<PanicOnDrop>) -> Vec<i32> {
+        list.iter().map(|item| item.value).collect()
+    }
+
+    #[test]
+    fn test_drop_frees_all_after_panic() {
+        let drops = Rc::new(Cell::new(0));
+        let list = create_list(10, &[6], &drops);
+        let result = catch_unwind(AssertUnwindSafe(move || drop(list)));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn test_pop_panicking_element() {
+        let drops = Rc::new(Cell::new(0));
+        let mut list = create_list(5, &[4], &drops);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.pop_back();
+        }));
+        assert!(result.is_err());
+        assert_eq!(list.validate(), Ok(()));
+        assert_eq!(values(&list), vec![0, 1, 2, 3]);
+        drop(list);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn test_retain_predicate_panics() {
+        let mut list = XorLinkedList::new();
+        for i in 0..10 {
+            list.push_back(i);
+        }
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.retain(|&x| {
+                assert!(x != 5, "predicate panics at 5");
+                x % 2 == 1
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(list.validate(), Ok(()));
+        assert_eq!(list.len(), 7);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 3, 5, 6, 7, 8, 9]
+        );
+        assert_eq!(
+            list.iter_rev().copied().collect::<Vec<_>>(),
+            vec![9, 8, 7, 6, 5, 3, 1]
+        );
+    }
+
+    #[test]
+    fn test_retain_drop_panics() {
+        let drops = Rc::new(Cell::new(0));
+        let mut list = create_list(10, &[4], &drops);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.retain(|item| item.value % 2 == 1);
+        }));
+        assert!(result.is_err());
+        assert_eq!(list.validate(), Ok(()));
+        // 0, 2 and the panicking 4 are gone, the rest was not visited yet
+        assert_eq!(drops.get(), 3);
+        assert_eq!(values(&list), vec![1, 3, 5, 6, 7, 8, 9]);
+
+        list.retain(|item| item.value % 2 == 1);
+        assert_eq!(values(&list), vec![1, 3, 5, 7, 9]);
+        drop(list);
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn test_skip_list_drop_frees_all_after_panic() {
+        let drops = Rc::new(Cell::new(0));
+        let mut map = XorSkipList::with_seed(3);
+        for key in 0..50 {
+            let value = PanicOnDrop {
+                value: key,
+                panics: key == 20,
+                drops: drops.clone(),
+            };
+            map.insert(key, value);
+        }
+        let result = catch_unwind(AssertUnwindSafe(move || drop(map)));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 50);
+    }
+}
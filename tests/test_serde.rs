@@ -0,0 +1,64 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use xor_linked_list::XorLinkedList;
+
+    fn create_list(size: usize) -> XorLinkedList<u64> {
+        let mut list = XorLinkedList::new();
+        for i in 0..size as u64 {
+            list.push_back(i * 3);
+        }
+        list
+    }
+
+    fn contents<T: Clone>(list: &XorLinkedList<T>) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        for size in [0, 1, 10_000] {
+            let list = create_list(size);
+            let json = serde_json::to_string(&list).unwrap();
+            assert_eq!(json, serde_json::to_string(&contents(&list)).unwrap());
+
+            let decoded: XorLinkedList<u64> = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded.len(), size);
+            assert_eq!(contents(&decoded), contents(&list));
+            assert_eq!(
+                decoded.iter_rev().next().copied(),
+                contents(&list).last().copied()
+            );
+        }
+    }
+
+    #[test]
+    fn test_bincode_round_trip() {
+        for size in [0, 1, 10_000] {
+            let list = create_list(size);
+            let bytes = bincode::serialize(&list).unwrap();
+            assert_eq!(bytes, bincode::serialize(&contents(&list)).unwrap());
+
+            let decoded: XorLinkedList<u64> = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(decoded.len(), size);
+            assert_eq!(contents(&decoded), contents(&list));
+        }
+    }
+
+    #[test]
+    fn test_nested_round_trip() {
+        let mut list = XorLinkedList::new();
+        list.push_back(vec![String::from("a"), String::from("b")]);
+        list.push_back(Vec::new());
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, r#"[["a","b"],[]]"#);
+
+        let decoded: XorLinkedList<Vec<String>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(contents(&decoded), contents(&list));
+    }
+
+    #[test]
+    fn test_json_not_a_sequence() {
+        assert!(serde_json::from_str::<XorLinkedList<u64>>("{\"a\": 1}").is_err());
+        assert!(serde_json::from_str::<XorLinkedList<u64>>("[1, \"two\"]").is_err());
+    }
+}
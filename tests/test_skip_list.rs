@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use xor_linked_list::{LevelGenerator, XorSkipList};
+
+    #[test]
+    fn test_new() {
+        let list = XorSkipList::<i32, i32>::new();
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.get(&1), None);
+    }
+
+    #[test]
+    fn test_insert_get() {
+        let mut list = XorSkipList::new();
+        assert_eq!(None, list.insert(2, "two"));
+        assert_eq!(None, list.insert(1, "one"));
+        assert_eq!(None, list.insert(3, "three"));
+        assert_eq!(list.len(), 3);
+
+        assert_eq!(Some(&"one"), list.get(&1));
+        assert_eq!(Some(&"two"), list.get(&2));
+        assert_eq!(Some(&"three"), list.get(&3));
+        assert_eq!(None, list.get(&4));
+        assert!(list.contains_key(&2));
+        assert!(!list.contains_key(&0));
+    }
+
+    #[test]
+    fn test_insert_replaces() {
+        let mut list = XorSkipList::new();
+        list.insert(1, 10);
+        assert_eq!(Some(10), list.insert(1, 20));
+        assert_eq!(list.len(), 1);
+        assert_eq!(Some(&20), list.get(&1));
+
+        *list.get_mut(&1).unwrap() += 1;
+        assert_eq!(Some(&21), list.get(&1));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut list = XorSkipList::new();
+        for i in 0..10 {
+            list.insert(i, i * 10);
+        }
+        assert_eq!(Some(50), list.remove(&5));
+        assert_eq!(None, list.remove(&5));
+        assert_eq!(Some(0), list.remove(&0));
+        assert_eq!(Some(90), list.remove(&9));
+        assert_eq!(list.len(), 7);
+
+        let keys: Vec<i32> = list.iter().map(|(&k, _)| k).collect();
+        assert_eq!(keys, vec![1, 2, 3, 4, 6, 7, 8]);
+
+        for i in 0..10 {
+            list.remove(&i);
+        }
+        assert!(list.is_empty());
+        assert_eq!(list.height(), 0);
+        assert_eq!(list.iter().next(), None);
+    }
+
+    #[test]
+    fn test_iter_ordered() {
+        let mut list = XorSkipList::with_seed(7);
+        for i in [5, 3, 8, 1, 9, 2, 7, 4, 6, 0] {
+            list.insert(i, i * 2);
+        }
+
+        let pairs: Vec<(i32, i32)> = list.iter().map(|(&k, &v)| (k, v)).collect();
+        let expected: Vec<(i32, i32)> = (0..10).map(|i| (i, i * 2)).collect();
+        assert_eq!(pairs, expected);
+
+        let keys: Vec<i32> = list.iter_rev().map(|(&k, _)| k).collect();
+        assert_eq!(keys, (0..10).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_against_btree_map() {
+        use std::collections::BTreeMap;
+
+        let mut list = XorSkipList::with_seed(42);
+        let mut map = BTreeMap::new();
+        let mut generator = LevelGenerator::new(1);
+        for step in 0..5_000u64 {
+            let key = (step * 7919 + generator.next_level() as u64) % 1_000;
+            if step % 3 == 0 {
+                assert_eq!(map.remove(&key), list.remove(&key));
+            } else {
+                assert_eq!(map.insert(key, step), list.insert(key, step));
+            }
+        }
+
+        assert_eq!(list.len(), map.len());
+        assert!(list.iter().eq(map.iter()));
+        assert!(list.iter_rev().eq(map.iter().rev()));
+        for key in 0..1_000 {
+            assert_eq!(list.get(&key), map.get(&key));
+        }
+    }
+
+    #[test]
+    fn test_seed_is_deterministic() {
+        let mut a = LevelGenerator::new(1234);
+        let mut b = LevelGenerator::new(1234);
+        for _ in 0..100 {
+            assert_eq!(a.next_level(), b.next_level());
+        }
+
+        let mut list_a = XorSkipList::with_seed(99);
+        let mut list_b = XorSkipList::with_seed(99);
+        for i in 0..1_000 {
+            list_a.insert(i, ());
+            list_b.insert(i, ());
+            assert_eq!(list_a.height(), list_b.height());
+        }
+        assert!(list_a.height() > 1);
+    }
+
+    #[test]
+    fn test_drop_owned_values() {
+        let mut list = XorSkipList::new();
+        for i in 0..100 {
+            list.insert(i.to_string(), vec![i; 4]);
+        }
+        assert_eq!(Some(vec![3; 4]), list.remove(&"3".to_string()));
+        assert_eq!(list.len(), 99);
+    }
+}
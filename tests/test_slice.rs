@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use xor_linked_list::{XorLinkedList, XorSlice};
+
+    fn create_list(size: usize) -> XorLinkedList<usize> {
+        let mut list = XorLinkedList::new();
+        for i in 0..size {
+            list.push_back(i);
+        }
+        list
+    }
+
+    fn sum(slice: XorSlice<'_, usize>) -> usize {
+        slice.iter().sum()
+    }
+
+    #[test]
+    fn test_slice() {
+        let list = create_list(10);
+        let slice = list.slice(2..7);
+        assert_eq!(slice.len(), 5);
+        assert_eq!(
+            slice.iter().copied().collect::<Vec<_>>(),
+            vec![2, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            slice.iter().rev().copied().collect::<Vec<_>>(),
+            vec![6, 5, 4, 3, 2]
+        );
+        assert_eq!(sum(slice), 20);
+
+        assert_eq!(list.slice(..).len(), 10);
+        assert_eq!(list.slice(..=3).iter().count(), 4);
+        assert_eq!(list.slice(8..).first(), Some(&8));
+        assert!(list.slice(4..4).is_empty());
+        assert_eq!(list.slice(4..4).iter().next(), None);
+    }
+
+    #[test]
+    fn test_slice_access() {
+        let list = create_list(10);
+        let slice = list.slice(1..9);
+        assert_eq!(slice.first(), Some(&1));
+        assert_eq!(slice.last(), Some(&8));
+        for i in 0..8 {
+            assert_eq!(slice.get(i), Some(&(i + 1)));
+        }
+        assert_eq!(slice.get(8), None);
+
+        let empty = list.slice(3..3);
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+    }
+
+    #[test]
+    fn test_slice_split_at() {
+        let list = create_list(10);
+        for mid in 0..=6 {
+            let (first, second) = list.slice(2..8).split_at(mid);
+            assert_eq!(first.len(), mid);
+            assert_eq!(second.len(), 6 - mid);
+            assert_eq!(
+                first.iter().copied().collect::<Vec<_>>(),
+                (2..2 + mid).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                second.iter().rev().copied().collect::<Vec<_>>(),
+                (2 + mid..8).rev().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_slice_out_of_bounds() {
+        let list = create_list(3);
+        let _ = list.slice(1..4);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end at usize::MAX out of bounds")]
+    fn test_slice_inclusive_end_overflow() {
+        let list = create_list(3);
+        let _ = list.slice(..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start after usize::MAX out of bounds")]
+    fn test_slice_excluded_start_overflow() {
+        use std::ops::Bound;
+        let list = create_list(3);
+        let _ = list.slice((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    fn test_slice_mut() {
+        let mut list = create_list(10);
+        let slice = list.slice_mut(2..8);
+        let (mut first, mut second) = slice.split_at(3);
+        for x in first.iter_mut() {
+            *x *= 10;
+        }
+        *second.first_mut().unwrap() = 0;
+        *second.last_mut().unwrap() = 0;
+        *second.get_mut(1).unwrap() = 1;
+        assert_eq!(second.get(1), Some(&1));
+        assert_eq!(
+            first.iter().rev().copied().collect::<Vec<_>>(),
+            vec![40, 30, 20]
+        );
+
+        for x in list.slice_mut(..2) {
+            *x += 100;
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![100, 101, 20, 30, 40, 0, 1, 0, 8, 9]
+        );
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(all(test, feature = "snapshot"))]
+mod tests {
+    use xor_linked_list::{SnapshotError, XorLinkedList};
+
+    fn create_list(size: usize) -> XorLinkedList<u64> {
+        let mut list = XorLinkedList::new();
+        for i in 0..size as u64 {
+            list.push_back(i.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        }
+        list
+    }
+
+    fn snapshot(list: &XorLinkedList<u64>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        list.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        for size in [0, 1, 1_000] {
+            let list = create_list(size);
+            let bytes = snapshot(&list);
+            assert_eq!(bytes.len(), 4 + 4 + 8 + 8 + size * 8 + 8);
+
+            let decoded = XorLinkedList::<u64>::read_from(&mut bytes.as_slice()).unwrap();
+            assert_eq!(decoded.len(), size);
+            assert!(decoded.iter().eq(list.iter()));
+            assert!(decoded.iter_rev().eq(list.iter_rev()));
+        }
+
+        let mut triples = XorLinkedList::new();
+        triples.push_back([1u16, 2, 3]);
+        triples.push_back([4, 5, 6]);
+        let mut bytes = Vec::new();
+        triples.write_to(&mut bytes).unwrap();
+        let decoded = XorLinkedList::<[u16; 3]>::read_from(&mut bytes.as_slice()).unwrap();
+        assert!(decoded.iter().eq(triples.iter()));
+    }
+
+    #[test]
+    fn test_snapshot_corruption() {
+        let bytes = snapshot(&create_list(10));
+
+        let mut flipped = bytes.clone();
+        flipped[30] ^= 1;
+        assert!(matches!(
+            XorLinkedList::<u64>::read_from(&mut flipped.as_slice()),
+            Err(SnapshotError::ChecksumMismatch { .. })
+        ));
+
+        let mut magic = bytes.clone();
+        magic[0] = b'Y';
+        assert!(matches!(
+            XorLinkedList::<u64>::read_from(&mut magic.as_slice()),
+            Err(SnapshotError::BadMagic(_))
+        ));
+
+        let mut version = bytes.clone();
+        version[4] = 2;
+        assert!(matches!(
+            XorLinkedList::<u64>::read_from(&mut version.as_slice()),
+            Err(SnapshotError::UnsupportedVersion(2))
+        ));
+
+        assert!(matches!(
+            XorLinkedList::<u32>::read_from(&mut bytes.as_slice()),
+            Err(SnapshotError::ElementSizeMismatch {
+                expected: 4,
+                found: 8
+            })
+        ));
+
+        let truncated = &bytes[..bytes.len() - 9];
+        let Err(error) = XorLinkedList::<u64>::read_from(&mut &truncated[..]) else {
+            panic!("truncated snapshot was accepted");
+        };
+        assert!(
+            matches!(&error, SnapshotError::Io(io) if io.kind() == std::io::ErrorKind::UnexpectedEof)
+        );
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_snapshot_zero_sized_length() {
+        let mut units = XorLinkedList::new();
+        for _ in 0..3 {
+            units.push_back(());
+        }
+        let mut bytes = Vec::new();
+        units.write_to(&mut bytes).unwrap();
+        let decoded = XorLinkedList::<()>::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(decoded.len(), 3);
+
+        // nothing is read per element, so only the checksum catches the length
+        let mut corrupt = bytes.clone();
+        corrupt[8..16].copy_from_slice(&(1u64 << 40).to_le_bytes());
+        assert!(matches!(
+            XorLinkedList::<()>::read_from(&mut corrupt.as_slice()),
+            Err(SnapshotError::ChecksumMismatch { .. })
+        ));
+
+        corrupt[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            XorLinkedList::<()>::read_from(&mut corrupt.as_slice()),
+            Err(SnapshotError::LengthTooLarge(u64::MAX))
+        ));
+    }
+}